@@ -0,0 +1,114 @@
+//! Files klox needs at runtime, built into the binary so it works wherever it's copied to.
+//!
+//! A file with the same name in the [assets directory](dir) takes the place of the built-in
+//! one, and if it won't load, the built-in one is used after all.
+
+use image::DynamicImage;
+use tracing::{debug, error};
+
+use std::path::{Path, PathBuf};
+
+/// Something built in, overridable by a file called [`Asset::name`]
+pub struct Asset {
+    pub name: &'static str,
+    bytes: &'static [u8],
+}
+
+/// Drawn behind the clock
+pub const BACKGROUND: Asset = Asset {
+    name: "background.png",
+    bytes: include_bytes!("../assets/background.png"),
+};
+
+impl Asset {
+    /// The file taking this one's place, if there is one
+    pub fn override_path(&self) -> Option<PathBuf> {
+        dir()
+            .map(|dir| dir.join(self.name))
+            .filter(|path| path.is_file())
+    }
+
+    /// The overriding file's image if there is one and it loads, otherwise the built-in one.
+    /// `None` if even that won't decode.
+    pub fn image(&self) -> Option<DynamicImage> {
+        match self.override_path() {
+            Some(path) => self.image_from(&path),
+            None => self.builtin_image(),
+        }
+    }
+
+    /// The image in `path`, or the built-in one if it won't load
+    pub fn image_from(&self, path: &Path) -> Option<DynamicImage> {
+        match image::open(path) {
+            Ok(img) => {
+                debug!("loaded {} from {path:?}", self.name);
+                Some(img)
+            }
+            Err(e) => {
+                error!(
+                    "couldn't load {path:?}, using the built-in {}: {e}",
+                    self.name
+                );
+                self.builtin_image()
+            }
+        }
+    }
+
+    fn builtin_image(&self) -> Option<DynamicImage> {
+        image::load_from_memory(self.bytes)
+            .map_err(|e| error!("couldn't decode the built-in {}: {e}", self.name))
+            .ok()
+    }
+}
+
+/// What to draw behind the clock
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Background {
+    /// [`BACKGROUND`], or whatever's overriding it
+    #[default]
+    Builtin,
+    /// This image, or the built-in one if it won't load
+    File(PathBuf),
+    /// Just the background color
+    Plain,
+}
+
+impl Background {
+    pub fn image(&self) -> Option<DynamicImage> {
+        match self {
+            Self::Builtin => BACKGROUND.image(),
+            Self::File(path) => BACKGROUND.image_from(path),
+            Self::Plain => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Background {
+    type Err = String;
+
+    /// Parse `builtin`, `plain` or the path to an image
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "builtin" => Ok(Self::Builtin),
+            "plain" => Ok(Self::Plain),
+            "" => Err("expected builtin, plain or the path to an image".to_string()),
+            path => Ok(Self::File(path.into())),
+        }
+    }
+}
+
+/// Where to look for files overriding the built-in assets: `KLOX_ASSETS` if it's set, otherwise
+/// the first `assets` directory found next to the executable or any directory above it, or
+/// failing that in the working directory
+pub fn dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("KLOX_ASSETS") {
+        return Some(dir.into());
+    }
+    let exe = std::env::current_exe().ok();
+    let cwd = std::env::current_dir().ok();
+    exe.iter()
+        .flat_map(|exe| exe.ancestors().skip(1))
+        .chain(cwd.as_deref())
+        .map(|dir| dir.join("assets"))
+        .find(|dir| dir.is_dir())
+}
@@ -0,0 +1,332 @@
+//! Running klox in Bevy: each clocklet is an entity with its face mesh and its two hands as
+//! children, posed from the [`Model`] every frame.
+//!
+//! The [`Model`] is the same one the window uses, moved on by Bevy's [`Time`], and the keys do
+//! the same things. Without Bevy's rendering plugins, e.g. with just [`MinimalPlugins`], the
+//! entities and their transforms are all still there, just never drawn.
+
+use bevy::ecs::event::EventCursor;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::clock::{Clocklet, Model, Style};
+use crate::config::{self, Config};
+use crate::geom;
+use crate::keys::{self, Action};
+use crate::render;
+use crate::watch::Watcher;
+
+/// Keeps a [`ClockModel`] (set up from [`Self::config`] unless one's already been inserted) up
+/// to date and in sync with a clocklet entity for every clocklet in it.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy::time::TimeUpdateStrategy;
+/// use klox::bevy_app::{ClockModel, ClockletIndex, Hand, KloxPlugin};
+/// use klox::clock::{Model, time_source::ManualTime, timezone::Zone};
+/// use std::time::Duration;
+/// use time::macros::datetime;
+///
+/// let time = ManualTime::new(datetime!(2025-06-01 12:34:56 UTC));
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, KloxPlugin::default()))
+///     .insert_resource(ClockModel(Model::with_time(Box::new(time), Zone::utc())))
+///     .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+/// app.finish();
+/// app.cleanup();
+/// for _ in 0..30 {
+///     app.update();
+/// }
+///
+/// let world = app.world_mut();
+/// assert_eq!(world.query::<&ClockletIndex>().iter(world).count(), 24);
+/// assert_eq!(world.query::<&Hand>().iter(world).count(), 48);
+/// ```
+#[derive(Default)]
+pub struct KloxPlugin {
+    pub config: Config,
+}
+
+impl Plugin for KloxPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<ClockModel>() {
+            app.insert_resource(ClockModel(Model::from_config(&self.config)));
+        }
+        match Watcher::new(config::path(), None) {
+            Ok(watcher) => {
+                app.insert_non_send_resource(watcher);
+            }
+            Err(e) => warn!("not watching for changes: {e}"),
+        }
+        app.insert_resource(ClearColor(bevy_color(self.config.background.color)))
+            .init_resource::<Bounds>()
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    reload,
+                    keys,
+                    fit_window,
+                    tick,
+                    recolor,
+                    spawn_clocklets,
+                    pose_clocklets,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The clock, as shared by every system
+#[derive(Resource, Deref, DerefMut)]
+pub struct ClockModel(pub Model);
+
+/// Where the model is laid out, in world coordinates, which are [`crate::geom`]'s with a 2D
+/// camera. Kept the size of the primary window, if there is one.
+#[derive(Resource)]
+pub struct Bounds(pub geom::Rect);
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Self(geom::Rect::from_w_h(1280.0, 480.0))
+    }
+}
+
+/// A clocklet, by where it comes in [`Model::layout`]. Its children are its [`Face`] and
+/// [`Hand`]s.
+#[derive(Component)]
+pub struct ClockletIndex(pub usize);
+
+/// A circle with a diameter of 1, scaled to the clocklet's size
+#[derive(Component)]
+pub struct Face;
+
+/// A bar from the clocklet's centre to 1 straight up, scaled to the hand's length and weight
+/// and turned to the hand's angle
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub enum Hand {
+    Hour,
+    Minute,
+}
+
+/// The meshes and materials everything's drawn with, if there's anything to draw with
+#[derive(Resource)]
+struct Shapes {
+    face: Handle<Mesh>,
+    hand: Handle<Mesh>,
+    face_color: Handle<ColorMaterial>,
+    hand_color: Handle<ColorMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    model: Res<ClockModel>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<ColorMaterial>>>,
+) {
+    commands.spawn(Camera2d);
+    if let (Some(mut meshes), Some(mut materials)) = (meshes, materials) {
+        let style = model.style();
+        commands.insert_resource(Shapes {
+            face: meshes.add(Circle::new(0.5)),
+            hand: meshes.add(Mesh::from(Rectangle::new(1.0, 1.0)).translated_by(Vec3::Y * 0.5)),
+            face_color: materials.add(bevy_color(style.face)),
+            hand_color: materials.add(bevy_color(style.hand)),
+        });
+    }
+}
+
+/// Apply the config file whenever it changes, keeping the clock where it is
+fn reload(
+    watcher: Option<NonSend<Watcher>>,
+    mut model: ResMut<ClockModel>,
+    mut clear: ResMut<ClearColor>,
+) {
+    let Some(config) = watcher.and_then(|watcher| watcher.changes().config) else {
+        return;
+    };
+    model.configure(&config);
+    clear.0 = bevy_color(config.background.color);
+}
+
+/// Keep the materials the colors the model says, which change with the config and while an
+/// alarm's flashing
+fn recolor(
+    model: Res<ClockModel>,
+    shapes: Option<Res<Shapes>>,
+    materials: Option<ResMut<Assets<ColorMaterial>>>,
+) {
+    let (Some(shapes), Some(mut materials)) = (shapes, materials) else {
+        return;
+    };
+    let style = model.style();
+    for (handle, color) in [
+        (&shapes.face_color, style.face),
+        (&shapes.hand_color, style.hand),
+    ] {
+        let color = bevy_color(color);
+        // Only touching them when they've changed, or they'd be re-uploaded every frame
+        if materials
+            .get(handle)
+            .is_some_and(|material| material.color != color)
+            && let Some(material) = materials.get_mut(handle)
+        {
+            material.color = color;
+        }
+    }
+}
+
+/// The keys, as in [`crate::keys`], by what they type so they're the same whatever the layout
+fn keys(
+    input: Option<Res<Events<KeyboardInput>>>,
+    mut cursor: Local<EventCursor<KeyboardInput>>,
+    mut model: ResMut<ClockModel>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(input) = input else {
+        return;
+    };
+    for event in cursor.read(&input) {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let key = match &event.logical_key {
+            Key::Character(s) => match s.chars().next() {
+                Some(c) => keys::Key::Char(c),
+                None => continue,
+            },
+            Key::Space => keys::Key::Char(' '),
+            Key::Enter => keys::Key::Enter,
+            Key::Backspace => keys::Key::Backspace,
+            Key::Escape => keys::Key::Escape,
+            _ => continue,
+        };
+        match Action::for_key(key) {
+            Some(Action::Quit) => {
+                exit.send(AppExit::Success);
+            }
+            Some(action) => action.apply(&mut model),
+            None => {}
+        }
+    }
+}
+
+fn fit_window(windows: Query<&Window, With<PrimaryWindow>>, mut bounds: ResMut<Bounds>) {
+    if let Ok(window) = windows.get_single() {
+        bounds.0 = geom::Rect::from_w_h(window.width(), window.height());
+    }
+}
+
+fn tick(time: Res<Time>, mut model: ResMut<ClockModel>) {
+    model.update(time.elapsed());
+}
+
+/// (Re)spawn the clocklets whenever there aren't as many as the model has, e.g. on startup or
+/// when seconds are turned on
+fn spawn_clocklets(
+    mut commands: Commands,
+    model: Res<ClockModel>,
+    bounds: Res<Bounds>,
+    shapes: Option<Res<Shapes>>,
+    clocklets: Query<Entity, With<ClockletIndex>>,
+) {
+    let layout = model.layout(bounds.0);
+    if clocklets.iter().count() == layout.len() {
+        return;
+    }
+    for entity in &clocklets {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    for (i, (rect, clocklet)) in layout.into_iter().enumerate() {
+        let pose = Pose::new(rect, clocklet, &model.style());
+        commands
+            .spawn((ClockletIndex(i), pose.clocklet, Visibility::default()))
+            .with_children(|parent| {
+                let mut face = parent.spawn((Face, pose.face));
+                if let Some(shapes) = &shapes {
+                    face.insert((
+                        Mesh2d(shapes.face.clone()),
+                        MeshMaterial2d(shapes.face_color.clone()),
+                    ));
+                }
+                for (hand, transform) in [(Hand::Hour, pose.hour), (Hand::Minute, pose.minute)] {
+                    let mut hand = parent.spawn((hand, transform));
+                    if let Some(shapes) = &shapes {
+                        hand.insert((
+                            Mesh2d(shapes.hand.clone()),
+                            MeshMaterial2d(shapes.hand_color.clone()),
+                        ));
+                    }
+                }
+            });
+    }
+}
+
+/// Move, size and turn every clocklet to match the model
+#[allow(clippy::type_complexity)]
+fn pose_clocklets(
+    model: Res<ClockModel>,
+    bounds: Res<Bounds>,
+    mut clocklets: Query<
+        (&ClockletIndex, &mut Transform, &Children),
+        (Without<Face>, Without<Hand>),
+    >,
+    mut faces: Query<&mut Transform, (With<Face>, Without<Hand>, Without<ClockletIndex>)>,
+    mut hands: Query<(&Hand, &mut Transform), (Without<Face>, Without<ClockletIndex>)>,
+) {
+    let layout = model.layout(bounds.0);
+    for (index, mut transform, children) in &mut clocklets {
+        let Some(&(rect, clocklet)) = layout.get(index.0) else {
+            continue;
+        };
+        let pose = Pose::new(rect, clocklet, &model.style());
+        *transform = pose.clocklet;
+        for &child in children {
+            if let Ok(mut face) = faces.get_mut(child) {
+                *face = pose.face;
+            } else if let Ok((hand, mut transform)) = hands.get_mut(child) {
+                *transform = match hand {
+                    Hand::Hour => pose.hour,
+                    Hand::Minute => pose.minute,
+                };
+            }
+        }
+    }
+}
+
+/// Where a clocklet and its parts go, with the same geometry as `Drawable for Clocklet`. The
+/// parts' transforms are relative to the clocklet's.
+struct Pose {
+    clocklet: Transform,
+    face: Transform,
+    hour: Transform,
+    minute: Transform,
+}
+
+impl Pose {
+    fn new(rect: geom::Rect, clocklet: Clocklet, style: &Style) -> Self {
+        let d = rect.w().min(rect.h());
+        let centre = rect.xy();
+        let (hour, minute) = clocklet.angles();
+        // Angles are clockwise, rotations about z anticlockwise. Hands go in front of the face.
+        let hand = |angle: f64| Transform {
+            translation: Vec3::Z,
+            rotation: Quat::from_rotation_z(-angle as f32),
+            scale: Vec3::new(style.hand_weight, d * style.hand_length, 1.0),
+        };
+        Self {
+            clocklet: Transform::from_xyz(centre.x, centre.y, 0.0),
+            face: Transform::from_scale(Vec3::new(d, d, 1.0)),
+            hour: hand(hour),
+            minute: hand(minute),
+        }
+    }
+}
+
+fn bevy_color(color: render::Color) -> Color {
+    let [r, g, b, a] = color.0;
+    Color::srgba_u8(r, g, b, a)
+}
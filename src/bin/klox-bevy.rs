@@ -0,0 +1,64 @@
+//! klox in a Bevy window, or with `--headless <frames>`, run for that many frames at 30 a second
+//! with Bevy's minimal plugins and no window at all, which is enough to check it works.
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use klox::bevy_app::{ClockletIndex, Hand, KloxPlugin};
+use klox::config::Config;
+use std::time::Duration;
+
+fn main() {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    let config = Config::load_default().unwrap_or_else(|e| {
+        eprintln!("klox-bevy: {e}");
+        std::process::exit(1);
+    });
+    match args.as_slice() {
+        [] => {
+            App::new()
+                .add_plugins(DefaultPlugins.set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: "klox".to_string(),
+                        ..default()
+                    }),
+                    ..default()
+                }))
+                .add_plugins(KloxPlugin { config })
+                .run();
+        }
+        [flag, frames] if flag == "--headless" => {
+            let frames: u32 = frames
+                .parse()
+                .unwrap_or_else(|e| usage(format!("bad value {frames:?} for --headless: {e}")));
+            tracing_subscriber::fmt::init();
+            headless(frames, config);
+        }
+        _ => usage("usage: klox-bevy [--headless <frames>]".to_string()),
+    }
+}
+
+fn headless(frames: u32, config: Config) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, KloxPlugin { config }))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(
+            Duration::from_secs(1) / 30,
+        ));
+    app.finish();
+    app.cleanup();
+    for _ in 0..frames {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let clocklets = world.query::<&ClockletIndex>().iter(world).count();
+    let hands = world.query::<&Hand>().iter(world).count();
+    println!("{frames} frames: {clocklets} clocklets, {hands} hands");
+    if clocklets == 0 || hands != clocklets * 2 {
+        std::process::exit(1);
+    }
+}
+
+fn usage(e: String) -> ! {
+    eprintln!("klox-bevy: {e}");
+    std::process::exit(2);
+}
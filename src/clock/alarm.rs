@@ -0,0 +1,233 @@
+//! Alarms: going off at a time of day, once or every week, until they're dismissed or snoozed.
+
+use time::{OffsetDateTime, PrimitiveDateTime, Time, Weekday};
+use tracing::{debug, info};
+
+use std::{fmt, str::FromStr, time::Duration};
+
+/// A time of day to go off at, on some days of the week, or just the next time it comes round.
+///
+/// ```
+/// use klox::clock::alarm::Alarm;
+/// use time::Weekday;
+///
+/// let alarm: Alarm = "07:30 mon,wed".parse().unwrap();
+/// assert_eq!(alarm.days, [Weekday::Monday, Weekday::Wednesday]);
+/// assert_eq!(alarm.to_string(), "07:30 mon,wed");
+///
+/// assert_eq!("6:00 weekends".parse::<Alarm>().unwrap().to_string(), "06:00 sat,sun");
+/// assert!("14:00".parse::<Alarm>().unwrap().days.is_empty());
+/// assert!("25:00".parse::<Alarm>().is_err());
+/// assert!("07:30 someday".parse::<Alarm>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alarm {
+    pub time: Time,
+    /// Every week on these days, or once if there aren't any
+    pub days: Vec<Weekday>,
+}
+
+impl Alarm {
+    const DAYS: [(&str, Weekday); 7] = [
+        ("mon", Weekday::Monday),
+        ("tue", Weekday::Tuesday),
+        ("wed", Weekday::Wednesday),
+        ("thu", Weekday::Thursday),
+        ("fri", Weekday::Friday),
+        ("sat", Weekday::Saturday),
+        ("sun", Weekday::Sunday),
+    ];
+
+    /// Whether it goes off at some point on `day`
+    fn is_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+}
+
+impl FromStr for Alarm {
+    type Err = String;
+
+    /// Parse `HH:MM`, optionally followed by a comma separated list of `mon` to `sun`,
+    /// `weekdays`, `weekends` or `daily`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (time, days) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+        let bad_time = || format!("expected HH:MM, got {time:?}");
+        let (hours, mins) = time.split_once(':').ok_or_else(bad_time)?;
+        let (hours, mins) = hours
+            .parse()
+            .ok()
+            .zip(mins.parse().ok())
+            .ok_or_else(bad_time)?;
+        let time = Time::from_hms(hours, mins, 0).map_err(|e| format!("bad time {time:?}: {e}"))?;
+
+        let mut alarm = Self {
+            time,
+            days: Vec::new(),
+        };
+        for day in days.split(',').map(str::trim).filter(|day| !day.is_empty()) {
+            let days = match day {
+                "daily" => &Self::DAYS[..],
+                "weekdays" => &Self::DAYS[..5],
+                "weekends" => &Self::DAYS[5..],
+                day => {
+                    let expected = "mon to sun, weekdays, weekends or daily";
+                    let i = Self::DAYS
+                        .iter()
+                        .position(|(name, _)| *name == day)
+                        .ok_or_else(|| format!("unknown day {day:?}, expected {expected}"))?;
+                    &Self::DAYS[i..=i]
+                }
+            };
+            for &(_, day) in days {
+                if !alarm.days.contains(&day) {
+                    alarm.days.push(day);
+                }
+            }
+        }
+        alarm.days.sort_by_key(|day| day.number_days_from_monday());
+        Ok(alarm)
+    }
+}
+
+impl fmt::Display for Alarm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.time.hour(), self.time.minute())?;
+        let names: Vec<_> = Self::DAYS
+            .iter()
+            .filter(|(_, day)| self.days.contains(day))
+            .map(|(name, _)| *name)
+            .collect();
+        if !names.is_empty() {
+            write!(f, " {}", names.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// Every alarm, checked against the local time as it goes by.
+///
+/// An alarm goes off when its time passes between one check and the next, so nothing goes off
+/// for times that had already gone when the clock started. Once one's going off, nothing else
+/// does until it's dismissed or snoozed.
+///
+/// ```
+/// use klox::clock::alarm::Alarms;
+/// use std::time::Duration;
+/// use time::macros::datetime;
+///
+/// let alarms = vec!["07:30 sat".parse().unwrap(), "08:00".parse().unwrap()];
+/// let mut alarms = Alarms::new(alarms, Duration::from_secs(9 * 60));
+///
+/// // 2025-06-07 is a Saturday
+/// assert!(!alarms.check(datetime!(2025-06-07 07:29:59 UTC)));
+/// assert!(alarms.check(datetime!(2025-06-07 07:30:00 UTC)));
+/// assert!(alarms.is_ringing());
+///
+/// alarms.snooze();
+/// assert!(!alarms.check(datetime!(2025-06-07 07:38:59 UTC)));
+/// assert!(alarms.check(datetime!(2025-06-07 07:39:00 UTC)));
+/// alarms.dismiss();
+///
+/// // The one-off only goes off once, the Saturday one not at all on Sunday
+/// assert!(alarms.check(datetime!(2025-06-07 08:00:00 UTC)));
+/// alarms.dismiss();
+/// assert!(!alarms.check(datetime!(2025-06-08 08:00:00 UTC)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Alarms {
+    alarms: Vec<Alarm>,
+    /// Which of the one-off alarms have gone off, by index
+    done: Vec<bool>,
+    snooze: Duration,
+    /// Local time as of the last check
+    last: Option<PrimitiveDateTime>,
+    snoozed_until: Option<PrimitiveDateTime>,
+    ringing: bool,
+}
+
+impl Alarms {
+    /// The default time a snooze lasts
+    pub const SNOOZE_MINUTES: u64 = 9;
+    /// How long the tint's on, then off, while an alarm's going off
+    pub const FLASH_MILLIS: u128 = 500;
+
+    pub fn new(alarms: Vec<Alarm>, snooze: Duration) -> Self {
+        Self {
+            done: vec![false; alarms.len()],
+            alarms,
+            snooze,
+            ..Default::default()
+        }
+    }
+
+    /// Go off at `alarms` from now on, snoozing for `snooze`. One-off alarms that have already
+    /// gone off stay that way as long as they're still there.
+    pub fn set(&mut self, alarms: Vec<Alarm>, snooze: Duration) {
+        if alarms != self.alarms {
+            self.done = alarms
+                .iter()
+                .map(|alarm| {
+                    self.alarms
+                        .iter()
+                        .zip(&self.done)
+                        .any(|(old, &done)| done && old == alarm)
+                })
+                .collect();
+            self.alarms = alarms;
+        }
+        self.snooze = snooze;
+    }
+
+    pub fn is_ringing(&self) -> bool {
+        self.ringing
+    }
+
+    /// Check what's gone off since the last check, given the local time `now`. Returns `true`
+    /// if an alarm's just started going off.
+    pub fn check(&mut self, now: OffsetDateTime) -> bool {
+        let now = PrimitiveDateTime::new(now.date(), now.time());
+        let Some(last) = self.last.replace(now) else {
+            return false;
+        };
+        if self.ringing || now <= last {
+            return false;
+        }
+        let due = |at: PrimitiveDateTime| last < at && at <= now;
+
+        let mut fired = self
+            .snoozed_until
+            .take_if(|&mut until| due(until))
+            .is_some();
+        let mut dates = vec![last.date()];
+        if now.date() != last.date() {
+            dates.push(now.date());
+        }
+        for (alarm, done) in self.alarms.iter().zip(self.done.iter_mut()) {
+            for &date in &dates {
+                if !*done && alarm.is_on(date.weekday()) && due(date.with_time(alarm.time)) {
+                    info!("⏰ alarm {alarm}");
+                    fired = true;
+                    *done = alarm.days.is_empty();
+                }
+            }
+        }
+        self.ringing = fired;
+        fired
+    }
+
+    /// Stop going off
+    pub fn dismiss(&mut self) {
+        debug!("⏰ dismissed");
+        self.ringing = false;
+    }
+
+    /// Stop going off, but go off again in a while
+    pub fn snooze(&mut self) {
+        if !self.ringing {
+            return;
+        }
+        debug!("⏰ snoozed for {:?}", self.snooze);
+        self.ringing = false;
+        self.snoozed_until = self.last.map(|last| last + self.snooze);
+    }
+}
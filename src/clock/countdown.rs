@@ -0,0 +1,169 @@
+//! Counting down to zero instead of telling the time, for standups and pomodoros.
+
+use tracing::debug;
+
+use std::time::Duration;
+
+use super::{
+    ClockTarget, Digit, Size,
+    readout::{self, Readout},
+};
+
+/// What the countdown wants doing to the clock
+#[derive(Debug)]
+pub enum CountdownEvent {
+    /// Show what's left
+    Show(ClockTarget),
+    /// It's just got to zero
+    Finished,
+}
+
+/// A countdown from [`Self::duration`], shown as MM:SS, or HH:MM while there's an hour or
+/// more left, as [`readout::digits`] writes it out rounded up. It's moved on by the same elapsed time as the animation, so it doesn't care what
+/// the wall clock does.
+///
+/// ```
+/// use klox::clock::{Size, countdown::{Countdown, CountdownEvent}};
+/// use std::time::Duration;
+///
+/// let secs = Duration::from_secs_f64;
+/// let size = Size::default();
+/// let mut countdown = Countdown::new(secs(90.0));
+///
+/// // Paused until it's started, but showing 01:30
+/// assert!(matches!(countdown.trigger(secs(0.0), size), Some(CountdownEvent::Show(_))));
+/// assert!(countdown.trigger(secs(5.0), size).is_none());
+///
+/// countdown.toggle();
+/// assert!(countdown.trigger(secs(10.0), size).is_none());
+/// // Rounded up, so it's 01:30 until it's a whole second down
+/// assert!(countdown.trigger(secs(10.5), size).is_none());
+/// assert!(countdown.trigger(secs(11.0), size).is_some());
+/// assert_eq!(countdown.remaining(), secs(89.0));
+///
+/// countdown.add_minute();
+/// assert!(countdown.trigger(secs(150.0), size).is_some());
+/// assert!(matches!(countdown.trigger(secs(160.0), size), Some(CountdownEvent::Finished)));
+/// assert!(countdown.trigger(secs(170.0), size).is_none());
+///
+/// countdown.reset();
+/// assert_eq!(countdown.remaining(), secs(90.0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Countdown {
+    duration: Duration,
+    remaining: Duration,
+    running: bool,
+    /// Elapsed time as of the last trigger while running
+    last: Option<Duration>,
+    readout: Readout,
+}
+
+impl Countdown {
+    /// How many scrambles [`super::Model`] plays at the finish, before settling on 00:00
+    pub const FINISH_SCRAMBLES: usize = 4;
+    /// How long each of those takes
+    pub const FINISH_SCRAMBLE_MILLIS: u64 = 700;
+
+    /// A countdown from `duration`, paused at the start
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            remaining: duration,
+            running: false,
+            last: None,
+            readout: Readout::default(),
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Start if it's paused, pause if it's running. Nothing to start once it's finished.
+    pub fn toggle(&mut self) {
+        self.running = !self.running && !self.remaining.is_zero();
+        self.last = None;
+    }
+
+    /// Back to the start, paused
+    pub fn reset(&mut self) {
+        self.remaining = self.duration;
+        self.running = false;
+        self.last = None;
+    }
+
+    /// Another minute to go, even once it's finished
+    pub fn add_minute(&mut self) {
+        self.remaining += Duration::from_secs(60);
+    }
+
+    /// Show it all again on the next trigger, e.g. after the clock's been resized
+    pub fn redraw(&mut self) {
+        self.readout.redraw();
+    }
+
+    /// Count down to `elapsed`, the time since the animation started, and say what to do to a
+    /// clock of the given `size`, if anything
+    pub fn trigger(&mut self, elapsed: Duration, size: Size) -> Option<CountdownEvent> {
+        if self.running {
+            let last = self.last.replace(elapsed).unwrap_or(elapsed);
+            self.remaining = self.remaining.saturating_sub(elapsed.saturating_sub(last));
+            if self.remaining.is_zero() {
+                debug!("⏳ countdown finished");
+                self.running = false;
+                // The finish shows 00:00 itself
+                self.readout.assume(self.digits());
+                return Some(CountdownEvent::Finished);
+            }
+        }
+
+        self.readout
+            .show(self.digits(), size)
+            .map(CountdownEvent::Show)
+    }
+
+    /// What's left, rounded up so it only shows 00:00 once it's finished
+    fn digits(&self) -> [Digit; 4] {
+        let secs = self.remaining.as_secs() + u64::from(self.remaining.subsec_nanos() > 0);
+        readout::digits(secs, true)
+    }
+}
+
+/// Parse `[[HH:]MM:]SS`, e.g. `25:00` or `1:30:00`
+///
+/// ```
+/// use klox::clock::countdown::parse_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_duration("25:00"), Ok(Duration::from_secs(25 * 60)));
+/// assert_eq!(parse_duration("1:30:00"), Ok(Duration::from_secs(90 * 60)));
+/// assert_eq!(parse_duration("45"), Ok(Duration::from_secs(45)));
+/// assert!(parse_duration("0:00").is_err());
+/// assert!(parse_duration("25m").is_err());
+/// ```
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let parts: Vec<_> = s.trim().split(':').collect();
+    if parts.len() > 3 {
+        return Err(format!("expected [[HH:]MM:]SS, got {s:?}"));
+    }
+    let mut secs = 0;
+    for part in parts {
+        let n: u64 = part
+            .parse()
+            .map_err(|e| format!("bad duration {s:?}: {e}"))?;
+        secs = secs * 60 + n;
+    }
+    if secs == 0 {
+        return Err("a countdown needs to be longer than 0".to_string());
+    }
+    Ok(Duration::from_secs(secs))
+}
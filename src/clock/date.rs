@@ -0,0 +1,187 @@
+//! Showing the date now and then, in between telling the time.
+
+use time::OffsetDateTime;
+use tracing::debug;
+
+use std::{str::FromStr, time::Duration};
+
+use super::{ClockTarget, Digit, Lifespan, Size};
+
+/// Which way round the day and month go
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DateOrder {
+    /// DD.MM
+    #[default]
+    DayMonth,
+    /// MM/DD
+    MonthDay,
+}
+
+impl FromStr for DateOrder {
+    type Err = String;
+
+    /// Parse `dd.mm` or `mm/dd`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "dd.mm" => Ok(Self::DayMonth),
+            "mm/dd" => Ok(Self::MonthDay),
+            s => Err(format!("expected dd.mm or mm/dd, got {s:?}")),
+        }
+    }
+}
+
+impl ClockTarget {
+    /// Display `date`'s day and month in `order`, centred in a grid of `size`
+    pub fn from_date(
+        date: &OffsetDateTime,
+        order: DateOrder,
+        size: Size,
+        lifespan: Lifespan,
+    ) -> Self {
+        let (day, month) = (date.day(), u8::from(date.month()));
+        let (first, second) = match order {
+            DateOrder::DayMonth => (day, month),
+            DateOrder::MonthDay => (month, day),
+        };
+        let digits = [first / 10, first, second / 10, second].map(Digit::from);
+        Self::showing(&digits, size, lifespan)
+    }
+}
+
+/// What the date wants doing to the clock
+#[derive(Debug)]
+pub enum DateEvent {
+    /// Put the date up
+    Show(ClockTarget),
+    /// The date's been up long enough, back to the time
+    Hide,
+}
+
+/// Decides when the date goes up and comes down again: for a while in the middle of every so
+/// often, lined up with midnight, and whenever asked.
+///
+/// While the date's up, whatever tells the time should leave the clock alone, then resync once
+/// it's hidden again.
+///
+/// ```
+/// use klox::clock::{Size, date::{DateEvent, DateOrder, TriggerDate}};
+/// use std::time::Duration;
+/// use time::macros::datetime;
+///
+/// let mut trigger = TriggerDate::new(DateOrder::DayMonth);
+/// trigger.set_schedule(Some(Duration::from_secs(60)), Duration::from_secs(10));
+/// let size = Size::default();
+///
+/// // Up from 25 to 35 seconds past each minute
+/// assert!(trigger.trigger(datetime!(2025-06-01 12:00:24 UTC), size).is_none());
+/// let event = trigger.trigger(datetime!(2025-06-01 12:00:25 UTC), size);
+/// assert!(matches!(event, Some(DateEvent::Show(_))));
+/// assert!(trigger.trigger(datetime!(2025-06-01 12:00:30 UTC), size).is_none());
+/// let event = trigger.trigger(datetime!(2025-06-01 12:00:35 UTC), size);
+/// assert!(matches!(event, Some(DateEvent::Hide)));
+/// assert!(trigger.trigger(datetime!(2025-06-01 12:00:40 UTC), size).is_none());
+///
+/// // Or whenever asked
+/// trigger.show();
+/// let event = trigger.trigger(datetime!(2025-06-01 12:00:40 UTC), size);
+/// assert!(matches!(event, Some(DateEvent::Show(_))));
+/// assert!(trigger.is_showing());
+/// ```
+pub struct TriggerDate {
+    order: DateOrder,
+    /// How often the date goes up by itself, if it does
+    every: Option<Duration>,
+    /// How long it stays up
+    hold: Duration,
+    /// How long to take getting there
+    sync_time: Duration,
+    /// Asked for by [`Self::show`], for the next trigger
+    requested: bool,
+    /// When the date that's up comes down
+    until: Option<OffsetDateTime>,
+}
+
+impl TriggerDate {
+    /// The default time the date stays up
+    pub const HOLD_SECONDS: u64 = 10;
+
+    /// Showing the date in `order`, only when asked
+    pub fn new(order: DateOrder) -> Self {
+        Self {
+            order,
+            every: None,
+            hold: Duration::from_secs(Self::HOLD_SECONDS),
+            sync_time: Duration::from_millis(super::TriggerTime::SYNC_MILLIS),
+            requested: false,
+            until: None,
+        }
+    }
+
+    pub fn order(&self) -> DateOrder {
+        self.order
+    }
+
+    /// Show the date in `order` from the next time it goes up
+    pub fn set_order(&mut self, order: DateOrder) {
+        self.order = order;
+    }
+
+    /// Put the date up for `hold` every `every` by itself, or only when asked if `every` is
+    /// `None`
+    pub fn set_schedule(&mut self, every: Option<Duration>, hold: Duration) {
+        self.every = every;
+        self.hold = hold;
+    }
+
+    /// Set how long getting to the date takes
+    pub fn set_sync_time(&mut self, sync_time: Duration) {
+        self.sync_time = sync_time;
+    }
+
+    /// Put the date up on the next trigger, however long until it's due
+    pub fn show(&mut self) {
+        self.requested = true;
+    }
+
+    /// Whether the date's up, or about to be
+    pub fn is_showing(&self) -> bool {
+        self.requested || self.until.is_some()
+    }
+
+    /// What to do to a clock of the given `size` at `now`, local time, if anything
+    pub fn trigger(&mut self, now: OffsetDateTime, size: Size) -> Option<DateEvent> {
+        if let Some(until) = self.until {
+            if now < until {
+                return None;
+            }
+            debug!("📅 back to the time");
+            self.until = None;
+            return Some(DateEvent::Hide);
+        }
+
+        let left = match std::mem::take(&mut self.requested) {
+            true => self.hold,
+            false => self.scheduled(now)?,
+        };
+        debug!("📅 showing the date for {left:?}");
+        self.until = Some(now + left);
+        let lifespan = Lifespan::Pending(self.sync_time.min(left));
+        Some(DateEvent::Show(ClockTarget::from_date(
+            &now, self.order, size, lifespan,
+        )))
+    }
+
+    /// How much longer the date's due up for at `now`, if it's due at all
+    fn scheduled(&self, now: OffsetDateTime) -> Option<Duration> {
+        let every = self.every?.as_secs().max(1);
+        let hold = self.hold.as_secs().min(every);
+        let (hours, mins, secs) = now.to_hms();
+        let since_midnight = u64::from(hours) * 3600 + u64::from(mins) * 60 + u64::from(secs);
+        let phase = since_midnight % every;
+        let start = (every - hold) / 2;
+        (start..start + hold)
+            .contains(&phase)
+            .then(|| Duration::from_secs(start + hold - phase))
+            .map(|left| left.saturating_sub(Duration::from_nanos(now.nanosecond().into())))
+    }
+}
@@ -0,0 +1,79 @@
+//! Which way round the hands go on their way to a target.
+
+use std::str::FromStr;
+
+/// Which hand of a clocklet we're moving
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hand {
+    Hour,
+    Minute,
+}
+
+/// A policy for turning hands towards their target positions.
+///
+/// Positions are fractions of a full turn, clockwise from 12 o'clock.
+///
+/// ```
+/// use klox::clock::direction::{Direction, Hand};
+///
+/// let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+///
+/// // 0.9 to 0.1 is quickest forwards, through 12 o'clock
+/// assert!(close(Direction::Shortest.travel(0.9, 0.1, 0.0, Hand::Hour), 0.2));
+/// assert!(close(Direction::CounterClockwise.travel(0.9, 0.1, 0.0, Hand::Hour), -0.8));
+///
+/// // Extra turns go the same way as the hand
+/// assert!(close(Direction::Clockwise.travel(0.9, 0.1, 2.0, Hand::Hour), 2.2));
+///
+/// // Opposing hands split up
+/// assert!(close(Direction::Opposing.travel(0.0, 0.5, 0.0, Hand::Hour), 0.5));
+/// assert!(close(Direction::Opposing.travel(0.0, 0.5, 0.0, Hand::Minute), -0.5));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// Whichever way is quicker
+    #[default]
+    Shortest,
+    Clockwise,
+    CounterClockwise,
+    /// Hour hands clockwise, minute hands counter-clockwise
+    Opposing,
+}
+
+impl Direction {
+    /// How far (in turns, clockwise positive) `hand` should travel to get from `from` to
+    /// `to`, taking `extra` whole turns on the way
+    pub fn travel(self, from: f64, to: f64, extra: f64, hand: Hand) -> f64 {
+        let clockwise = (to - from).rem_euclid(1.0);
+        let counter_clockwise = if clockwise > 0.0 {
+            clockwise - 1.0
+        } else {
+            0.0
+        };
+        match (self, hand) {
+            (Self::Shortest, _) if clockwise <= 0.5 => clockwise + extra,
+            (Self::Shortest, _) => counter_clockwise - extra,
+            (Self::Clockwise, _) | (Self::Opposing, Hand::Hour) => clockwise + extra,
+            (Self::CounterClockwise, _) | (Self::Opposing, Hand::Minute) => {
+                counter_clockwise - extra
+            }
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "shortest" => Ok(Self::Shortest),
+            "clockwise" => Ok(Self::Clockwise),
+            "counter-clockwise" => Ok(Self::CounterClockwise),
+            "opposing" => Ok(Self::Opposing),
+            _ => Err(format!(
+                "unknown direction {s:?}, expected one of shortest, clockwise, \
+                 counter-clockwise, opposing"
+            )),
+        }
+    }
+}
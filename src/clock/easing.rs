@@ -0,0 +1,211 @@
+//! Easing curves, for shaping how a [`super::ClockTarget`]'s progress runs from 0 to 1.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::str::FromStr;
+
+/// The basic shapes, defined as "ease in" curves. [`Easing`] derives the rest from these.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Curve {
+    Cubic,
+    Sine,
+    /// Pulls back a little before setting off
+    Back,
+    /// Wobbles like it's on a spring
+    Elastic,
+    /// Bounces off the end it's heading towards
+    Bounce,
+}
+
+impl Curve {
+    fn ease_in(self, t: f64) -> f64 {
+        match self {
+            Self::Cubic => t * t * t,
+            Self::Sine => 1.0 - (t * FRAC_PI_2).cos(),
+            Self::Back => {
+                const C1: f64 = 1.70158;
+                const C3: f64 = C1 + 1.0;
+                C3 * t * t * t - C1 * t * t
+            }
+            Self::Elastic => {
+                if t <= 0.0 || t >= 1.0 {
+                    return t;
+                }
+                -(2f64.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * TAU / 3.0).sin()
+            }
+            Self::Bounce => 1.0 - bounce_out(1.0 - t),
+        }
+    }
+}
+
+fn bounce_out(t: f64) -> f64 {
+    const N1: f64 = 7.5625;
+    const D1: f64 = 2.75;
+    if t < 1.0 / D1 {
+        N1 * t * t
+    } else if t < 2.0 / D1 {
+        let t = t - 1.5 / D1;
+        N1 * t * t + 0.75
+    } else if t < 2.5 / D1 {
+        let t = t - 2.25 / D1;
+        N1 * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D1;
+        N1 * t * t + 0.984375
+    }
+}
+
+/// Maps linear progress through a transition to how far the hands should have got.
+///
+/// Some curves overshoot, so the output can stray outside 0..=1 on the way, but always starts
+/// at 0 and finishes at 1.
+///
+/// ```
+/// use klox::clock::easing::{Curve, Easing};
+///
+/// let easing: Easing = "in-out-cubic".parse().unwrap();
+/// assert_eq!(easing, Easing::InOut(Curve::Cubic));
+/// assert_eq!(easing.apply(0.0), 0.0);
+/// assert_eq!(easing.apply(0.5), 0.5);
+/// assert_eq!(easing.apply(1.0), 1.0);
+///
+/// assert!(Easing::In(Curve::Back).apply(0.2) < 0.0);
+///
+/// let css_ease: Easing = "cubic-bezier(0.25, 0.1, 0.25, 1.0)".parse().unwrap();
+/// assert!((css_ease.apply(0.5) - 0.8024).abs() < 1e-3);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    In(Curve),
+    Out(Curve),
+    InOut(Curve),
+    /// Like CSS's `cubic-bezier(x1, y1, x2, y2)`, with control points (x1, y1) and (x2, y2)
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl Easing {
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::In(curve) => curve.ease_in(t),
+            Self::Out(curve) => 1.0 - curve.ease_in(1.0 - t),
+            Self::InOut(curve) => {
+                if t < 0.5 {
+                    curve.ease_in(2.0 * t) / 2.0
+                } else {
+                    1.0 - curve.ease_in(2.0 - 2.0 * t) / 2.0
+                }
+            }
+            Self::CubicBezier(x1, y1, x2, y2) => {
+                let s = solve_bezier(t, x1, x2);
+                bezier(s, y1, y2)
+            }
+        }
+    }
+}
+
+/// One coordinate of a cubic bezier from 0 to 1 with control points `p1` and `p2`
+fn bezier(s: f64, p1: f64, p2: f64) -> f64 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+}
+
+fn bezier_slope(s: f64, p1: f64, p2: f64) -> f64 {
+    let r = 1.0 - s;
+    3.0 * r * r * p1 + 6.0 * r * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+}
+
+/// Find the curve parameter at which the x coordinate is `x`.
+/// Newton's method usually gets there in a few steps; bisect if it doesn't.
+fn solve_bezier(x: f64, x1: f64, x2: f64) -> f64 {
+    const EPSILON: f64 = 1e-7;
+
+    let mut s = x;
+    for _ in 0..8 {
+        let err = bezier(s, x1, x2) - x;
+        if err.abs() < EPSILON {
+            return s;
+        }
+        let slope = bezier_slope(s, x1, x2);
+        if slope.abs() < EPSILON {
+            break;
+        }
+        s -= err / slope;
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    s = x;
+    while hi - lo > EPSILON {
+        if bezier(s, x1, x2) < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    s
+}
+
+impl FromStr for Curve {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cubic" => Ok(Self::Cubic),
+            "sine" => Ok(Self::Sine),
+            "back" => Ok(Self::Back),
+            "elastic" => Ok(Self::Elastic),
+            "bounce" => Ok(Self::Bounce),
+            _ => Err(format!(
+                "unknown curve {s:?}, expected one of cubic, sine, back, elastic, bounce"
+            )),
+        }
+    }
+}
+
+impl FromStr for Easing {
+    type Err = String;
+
+    /// Parse `linear`, `in-<curve>`, `out-<curve>`, `in-out-<curve>` or
+    /// `cubic-bezier(x1, y1, x2, y2)`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "linear" {
+            return Ok(Self::Linear);
+        }
+        if let Some(args) = s
+            .strip_prefix("cubic-bezier(")
+            .and_then(|s| s.strip_suffix(')'))
+        {
+            let points = args
+                .split(',')
+                .map(|p| p.trim().parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("bad cubic-bezier point in {s:?}: {e}"))?;
+            let [x1, y1, x2, y2] = points[..] else {
+                return Err(format!("cubic-bezier needs 4 numbers, got {s:?}"));
+            };
+            if !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2) {
+                return Err(format!(
+                    "cubic-bezier x values must be within 0..=1, got {s:?}"
+                ));
+            }
+            return Ok(Self::CubicBezier(x1, y1, x2, y2));
+        }
+        if let Some(curve) = s.strip_prefix("in-out-") {
+            return Ok(Self::InOut(curve.parse()?));
+        }
+        if let Some(curve) = s.strip_prefix("in-") {
+            return Ok(Self::In(curve.parse()?));
+        }
+        if let Some(curve) = s.strip_prefix("out-") {
+            return Ok(Self::Out(curve.parse()?));
+        }
+        Err(format!(
+            "unknown easing {s:?}, expected linear, in-<curve>, out-<curve>, in-out-<curve> \
+             or cubic-bezier(x1, y1, x2, y2)"
+        ))
+    }
+}
@@ -1,6 +1,11 @@
-use nannou::image::GenericImageView;
-use nannou::prelude::*;
-use time::{OffsetDateTime, UtcOffset};
+//! The clock itself: digits, clocklets and the animation queue that moves their hands, plus
+//! the [`Model`] that keeps it telling the time.
+//!
+//! Nothing in here knows what it's being drawn on. Time moves on with a plain elapsed
+//! [`Duration`], and drawing goes through [`crate::render::Renderer`].
+
+use rand::random;
+use time::OffsetDateTime;
 use tracing::debug;
 
 use std::{
@@ -10,12 +15,49 @@ use std::{
     time::Duration,
 };
 
+use crate::config::Config;
+use crate::geom::{Point2, Rect};
+use crate::render::{Color, Renderer};
 use crate::{Drawable, RectUtils};
-
-struct Digit([Clocklet; 6]);
+use alarm::Alarms;
+use countdown::{Countdown, CountdownEvent};
+use date::{DateEvent, DateOrder, TriggerDate};
+use stopwatch::Stopwatch;
+use world::TriggerZone;
+
+pub mod alarm;
+pub mod countdown;
+pub mod date;
+pub mod direction;
+pub mod easing;
+pub mod pattern;
+pub mod readout;
+pub mod stagger;
+pub mod stopwatch;
+pub mod time_source;
+pub mod timeline;
+pub mod timezone;
+pub mod transition;
+pub mod world;
+
+use direction::{Direction, Hand};
+use easing::Easing;
+use pattern::Pattern;
+use stagger::{Stagger, Timings};
+use time_source::TimeSource;
+use timeline::Timeline;
+use timezone::Zone;
+use transition::{Transition, Transitions};
+
+/// 2 columns of 3 clocklets, listed column by column
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Digit([Clocklet; 6]);
 
 impl Digit {
-    const ZERO: Self = Self([
+    pub const COLUMNS: usize = 2;
+    pub const ROWS: usize = 3;
+
+    pub const ZERO: Self = Self([
         Clocklet::TL,
         Clocklet::V,
         Clocklet::BL,
@@ -23,7 +65,7 @@ impl Digit {
         Clocklet::V,
         Clocklet::BR,
     ]);
-    const ONE: Self = Self([
+    pub const ONE: Self = Self([
         Clocklet::BLANK,
         Clocklet::BLANK,
         Clocklet::BLANK,
@@ -31,7 +73,7 @@ impl Digit {
         Clocklet::V,
         Clocklet::U,
     ]);
-    const TWO: Self = Self([
+    pub const TWO: Self = Self([
         Clocklet::R,
         Clocklet::TL,
         Clocklet::BL,
@@ -39,7 +81,7 @@ impl Digit {
         Clocklet::BR,
         Clocklet::L,
     ]);
-    const THREE: Self = Self([
+    pub const THREE: Self = Self([
         Clocklet::R,
         Clocklet::R,
         Clocklet::R,
@@ -47,7 +89,7 @@ impl Digit {
         Clocklet::BR,
         Clocklet::BR,
     ]);
-    const FOUR: Self = Self([
+    pub const FOUR: Self = Self([
         Clocklet::D,
         Clocklet::BL,
         Clocklet::BLANK,
@@ -55,7 +97,7 @@ impl Digit {
         Clocklet::BR,
         Clocklet::U,
     ]);
-    const FIVE: Self = Self([
+    pub const FIVE: Self = Self([
         Clocklet::TL,
         Clocklet::BL,
         Clocklet::R,
@@ -63,7 +105,7 @@ impl Digit {
         Clocklet::TR,
         Clocklet::BR,
     ]);
-    const SIX: Self = Self([
+    pub const SIX: Self = Self([
         Clocklet::TL,
         Clocklet::V,
         Clocklet::BL,
@@ -71,7 +113,7 @@ impl Digit {
         Clocklet::TR,
         Clocklet::BR,
     ]);
-    const SEVEN: Self = Self([
+    pub const SEVEN: Self = Self([
         Clocklet::R,
         Clocklet::BLANK,
         Clocklet::BLANK,
@@ -79,7 +121,7 @@ impl Digit {
         Clocklet::V,
         Clocklet::U,
     ]);
-    const EIGHT: Self = Self([
+    pub const EIGHT: Self = Self([
         Clocklet::TL,
         Clocklet::BL,
         Clocklet::BL,
@@ -87,7 +129,7 @@ impl Digit {
         Clocklet::BR,
         Clocklet::BR,
     ]);
-    const NINE: Self = Self([
+    pub const NINE: Self = Self([
         Clocklet::TL,
         Clocklet::BL,
         Clocklet::R,
@@ -95,7 +137,7 @@ impl Digit {
         Clocklet::BR,
         Clocklet::BR,
     ]);
-    const BLANK: Self = Self([Clocklet::BLANK; 6]);
+    pub const BLANK: Self = Self([Clocklet::BLANK; 6]);
 }
 
 impl From<u8> for Digit {
@@ -116,6 +158,21 @@ impl From<u8> for Digit {
     }
 }
 
+impl TryFrom<char> for Digit {
+    type Error = String;
+
+    /// `'0'` to `'9'`, or a space for [`Digit::BLANK`]
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            ' ' => Ok(Digit::BLANK),
+            _ => c
+                .to_digit(10)
+                .map(|n| Digit::from(n as u8))
+                .ok_or_else(|| format!("{c:?} isn't a digit or a space")),
+        }
+    }
+}
+
 impl<'a> IntoIterator for &'a Digit {
     type Item = &'a Clocklet;
     type IntoIter = std::slice::Iter<'a, Clocklet>;
@@ -124,7 +181,7 @@ impl<'a> IntoIterator for &'a Digit {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Clocklet {
     /// hour hand expressed as fraction of a full turn
     hour_hand_turns: f64,
@@ -133,10 +190,19 @@ pub struct Clocklet {
 }
 
 impl Clocklet {
+    /// Hand length, as a fraction of the face diameter
+    pub const HAND_LENGTH: f32 = 0.45;
+    /// Hand thickness, in pixels
+    pub const HAND_WEIGHT: f32 = 5.0;
+
+    /// Returns (hour, minute) hand angles in radians, clockwise from 12 o'clock
+    pub fn angles(&self) -> (f64, f64) {
+        (TAU * self.hour_hand_turns, TAU * self.minute_hand_turns)
+    }
+
     /// Returns normal vectors corresponding to (a, b)
-    fn vectors(&self) -> (Point2, Point2) {
-        let a_rad = TAU * self.hour_hand_turns;
-        let b_rad = TAU * self.minute_hand_turns;
+    pub fn vectors(&self) -> (Point2, Point2) {
+        let (a_rad, b_rad) = self.angles();
         (
             Point2::new(a_rad.sin() as f32, a_rad.cos() as f32),
             Point2::new(b_rad.sin() as f32, b_rad.cos() as f32),
@@ -150,8 +216,42 @@ impl Clocklet {
         }
     }
 
+    /// Returns (hour, minute) hand positions as fractions of a full turn
+    pub fn turns(&self) -> (f64, f64) {
+        (self.hour_hand_turns, self.minute_hand_turns)
+    }
+
+    /// The same hand positions, with whole turns taken off so each is within `0.0..1.0`
+    pub fn normalized(self) -> Self {
+        Self {
+            hour_hand_turns: self.hour_hand_turns.rem_euclid(1.0),
+            minute_hand_turns: self.minute_hand_turns.rem_euclid(1.0),
+        }
+    }
+
+    /// How far each hand needs to turn to get to `to`, going the way `direction` says, plus
+    /// `extra` whole turns
+    pub fn travel(self, to: Self, direction: Direction, extra: f64) -> Self {
+        Self {
+            hour_hand_turns: direction.travel(
+                self.hour_hand_turns,
+                to.hour_hand_turns,
+                extra,
+                Hand::Hour,
+            ),
+            minute_hand_turns: direction.travel(
+                self.minute_hand_turns,
+                to.minute_hand_turns,
+                extra,
+                Hand::Minute,
+            ),
+        }
+    }
+
     pub const BL: Clocklet = Clocklet::from_turns(0.0, 0.25);
     pub const BLANK: Clocklet = Clocklet::from_turns(0.625, 0.625);
+    /// [`Self::BLANK`] mirrored, for telling PM from AM in 12-hour time
+    pub const BLANK_PM: Clocklet = Clocklet::from_turns(0.375, 0.375);
     pub const BR: Clocklet = Clocklet::from_turns(0.0, 0.75);
     pub const H: Clocklet = Clocklet::from_turns(0.25, 0.75);
     pub const L: Clocklet = Clocklet::from_turns(0.75, 0.75);
@@ -211,25 +311,56 @@ impl SubAssign<f64> for Clocklet {
 impl Default for Clocklet {
     fn default() -> Self {
         Self {
-            hour_hand_turns: random_f64(),
-            minute_hand_turns: random_f64(),
+            hour_hand_turns: random(),
+            minute_hand_turns: random(),
         }
     }
 }
 
-impl Drawable for Clocklet {
-    fn draw(&self, bounds: Rect, draw: &Draw) {
-        let d = partial_min(bounds.w(), bounds.h());
+impl Clocklet {
+    /// A face filling the smaller side of `bounds`, and two hands, as `style` says
+    pub fn draw_styled(&self, bounds: Rect, style: &Style, renderer: &mut dyn Renderer) {
+        let d = bounds.w().min(bounds.h());
         let o = bounds.xy();
-        draw.ellipse().xy(o).w_h(d, d).color(WHITE);
-        let r = d * 0.45;
+        renderer.face(o, d / 2.0, style.face);
+        let r = d * style.hand_length;
         let (hours, mins) = self.vectors();
-        draw.line().weight(5.0).start(o).end(o + r * hours);
-        draw.line().weight(5.0).start(o).end(o + r * mins);
+        renderer.hand(o, o + r * hours, style.hand_weight, style.hand);
+        renderer.hand(o, o + r * mins, style.hand_weight, style.hand);
     }
 }
 
-enum Lifespan {
+impl Drawable for Clocklet {
+    /// In the default [`Style`]
+    fn draw(&self, bounds: Rect, renderer: &mut dyn Renderer) {
+        self.draw_styled(bounds, &Style::default(), renderer);
+    }
+}
+
+/// What clocklets look like
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Style {
+    pub face: Color,
+    pub hand: Color,
+    /// As a fraction of the face diameter
+    pub hand_length: f32,
+    /// In pixels
+    pub hand_weight: f32,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            face: Color::WHITE,
+            hand: Color::BLACK,
+            hand_length: Clocklet::HAND_LENGTH,
+            hand_weight: Clocklet::HAND_WEIGHT,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Lifespan {
     Pending(Duration),
     Active {
         start: Duration,
@@ -244,12 +375,13 @@ impl Lifespan {
         Self::Pending(Duration::from_millis(millis))
     }
 
-    pub fn update(mut self, update: &Update) -> Self {
+    /// Move on to `elapsed`, the time since the animation started
+    pub fn update(mut self, elapsed: Duration) -> Self {
         if let Lifespan::Pending(deadline) = self {
             return Self::Active {
-                start: update.since_start,
-                current: update.since_start,
-                deadline: update.since_start + deadline,
+                start: elapsed,
+                current: elapsed,
+                deadline: elapsed + deadline,
             };
         }
         if let Lifespan::Active {
@@ -258,14 +390,11 @@ impl Lifespan {
             ..
         } = self
         {
-            if *deadline < update.since_start {
-                debug!(
-                    "{deadline:?} passed ({:?}), Lifespan -> Finished",
-                    update.since_start
-                );
+            if *deadline < elapsed {
+                debug!("{deadline:?} passed ({elapsed:?}), Lifespan -> Finished");
                 return Self::Finished;
             }
-            *current = update.since_start;
+            *current = elapsed;
         }
         self
     }
@@ -278,75 +407,392 @@ impl Default for Lifespan {
     }
 }
 
-#[derive(Default)]
-struct ClockTarget {
-    clocklets: [[Clocklet; 3]; 8],
-    extra_turns: Option<[[f64; 3]; 8]>,
+/// How finely the clock tells the time, which also decides how wide it needs to be
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Resolution {
+    /// HH:MM on 8 columns, changing each minute
+    #[default]
+    Minutes,
+    /// HH:MM:SS on 12 columns, changing each second
+    Seconds,
+}
+
+impl Resolution {
+    /// Number of digits on display
+    pub fn digits(self) -> usize {
+        match self {
+            Self::Minutes => 4,
+            Self::Seconds => 6,
+        }
+    }
+
+    /// Number of clocklet columns needed to display all the digits
+    pub fn columns(self) -> usize {
+        self.digits() * Digit::COLUMNS
+    }
+
+    /// The start of the minute or second `time` falls in
+    fn truncate(self, time: OffsetDateTime) -> OffsetDateTime {
+        let time = time.replace_nanosecond(0).expect("0 is a valid nanosecond");
+        match self {
+            Self::Minutes => time.replace_second(0).expect("0 is a valid second"),
+            Self::Seconds => time,
+        }
+    }
+}
+
+/// Whether the hours go round once a day or twice
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Hours {
+    /// 00 to 23
+    #[default]
+    TwentyFour,
+    /// 1 to 12, with a column after the digits posed as [`Clocklet::BLANK_PM`] in the
+    /// afternoon
+    Twelve,
+}
+
+impl std::str::FromStr for Hours {
+    type Err = String;
+
+    /// Parse `24h` or `12h`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "24h" => Ok(Self::TwentyFour),
+            "12h" => Ok(Self::Twelve),
+            s => Err(format!("expected 24h or 12h, got {s:?}")),
+        }
+    }
+}
+
+/// How the time is written out.
+///
+/// ```
+/// use klox::clock::{ClockTarget, Clocklet, Hours, Lifespan, Resolution, Size, TimeFormat};
+/// use time::macros::datetime;
+///
+/// let target = |time, format| {
+///     let resolution = Resolution::Minutes;
+///     let size = Size::default().fit(resolution, format);
+///     ClockTarget::from_time(&time, resolution, format, size, Lifespan::default())
+/// };
+///
+/// // 12-hour time gets a column after the digits saying which half of the day it is
+/// let twelve = TimeFormat { hours: Hours::Twelve, ..Default::default() };
+/// assert_eq!(Size::default().fit(Resolution::Minutes, twelve), Size::new(9, 3));
+/// for (pm, am) in [
+///     (datetime!(2025-06-01 20:35 UTC), datetime!(2025-06-01 08:35 UTC)),
+///     (datetime!(2025-06-01 22:00 UTC), datetime!(2025-06-01 10:00 UTC)),
+/// ] {
+///     let (pm, am) = (target(pm, twelve), target(am, twelve));
+///     assert_eq!(pm.clocklets()[8], [Clocklet::BLANK_PM; 3]);
+///     assert_eq!(am.clocklets()[8], [Clocklet::BLANK; 3]);
+///     // The digits themselves look the same either way
+///     assert_eq!(pm.clocklets()[..8], am.clocklets()[..8]);
+/// }
+///
+/// // " 9:05" with the leading zero turned off
+/// let no_zero = TimeFormat { leading_zero: false, ..twelve };
+/// let pm = target(datetime!(2025-06-01 21:05 UTC), no_zero);
+/// assert_eq!(pm.clocklets()[0], [Clocklet::BLANK; 3]);
+/// assert_eq!(pm.clocklets()[8], [Clocklet::BLANK_PM; 3]);
+///
+/// // No cue in 24-hour time, and a leading zero unless it's turned off
+/// let zero = target(datetime!(2025-06-01 09:05 UTC), TimeFormat::default());
+/// assert_eq!(zero.clocklets().len(), 8);
+/// assert_ne!(zero.clocklets()[0], [Clocklet::BLANK; 3]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeFormat {
+    pub hours: Hours,
+    /// `09:05` rather than ` 9:05`
+    pub leading_zero: bool,
+}
+
+impl TimeFormat {
+    /// Columns needed after the digits, for telling AM from PM
+    pub fn cue_columns(self) -> usize {
+        match self.hours {
+            Hours::TwentyFour => 0,
+            Hours::Twelve => 1,
+        }
+    }
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        Self {
+            hours: Hours::TwentyFour,
+            leading_zero: true,
+        }
+    }
+}
+
+/// Dimensions of the clocklet grid.
+///
+/// Anything at least 3 rows tall works; digits are drawn centred, as many as will fit.
+///
+/// ```
+/// use klox::clock::{Resolution, Size, TimeFormat};
+///
+/// let size: Size = "24x9".parse().unwrap();
+/// assert_eq!(size, Size::new(24, 9));
+///
+/// // Too narrow for seconds, so it's stretched
+/// let format = TimeFormat::default();
+/// assert_eq!(Size::default().fit(Resolution::Seconds, format), Size::new(12, 3));
+/// assert_eq!(size.fit(Resolution::Seconds, format), size);
+///
+/// assert!("24".parse::<Size>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Size {
+    pub columns: usize,
+    pub rows: usize,
+}
+
+impl Size {
+    pub const fn new(columns: usize, rows: usize) -> Self {
+        Self { columns, rows }
+    }
+
+    /// Grow if needed, so there's room to display the time at `resolution` as `format` says
+    pub fn fit(self, resolution: Resolution, format: TimeFormat) -> Self {
+        Self {
+            columns: self
+                .columns
+                .max(resolution.columns() + format.cue_columns()),
+            rows: self.rows.max(Digit::ROWS),
+        }
+    }
+
+    /// Width over height, in clocklets
+    pub fn aspect(self) -> f32 {
+        self.columns as f32 / self.rows as f32
+    }
+
+    /// Build a grid of columns of rows, calling `f(col, row)` for each cell
+    fn grid<T>(self, mut f: impl FnMut(usize, usize) -> T) -> Vec<Vec<T>> {
+        (0..self.columns)
+            .map(|col| (0..self.rows).map(|row| f(col, row)).collect())
+            .collect()
+    }
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Self::new(Resolution::default().columns(), Digit::ROWS)
+    }
+}
+
+impl std::str::FromStr for Size {
+    type Err = String;
+
+    /// Parse `<columns>x<rows>`, e.g. `24x9`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (columns, rows) = s
+            .split_once('x')
+            .ok_or_else(|| format!("expected <columns>x<rows>, got {s:?}"))?;
+        let parse = |n: &str| {
+            n.trim()
+                .parse::<usize>()
+                .map_err(|e| format!("bad grid dimension {n:?}: {e}"))
+        };
+        let size = Self::new(parse(columns)?, parse(rows)?);
+        if size.columns < Digit::COLUMNS || size.rows < Digit::ROWS {
+            return Err(format!(
+                "grid must be at least {}x{} to fit a digit",
+                Digit::COLUMNS,
+                Digit::ROWS
+            ));
+        }
+        Ok(size)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ClockTarget {
+    /// Columns of rows of clocklets, as in [`Clock`]
+    clocklets: Vec<Vec<Clocklet>>,
+    /// Whole turns for each clocklet to make on the way, on top of getting into position
+    extra_turns: Option<Vec<Vec<f64>>>,
     pub lifespan: Lifespan,
+    /// Shape of the transition towards this target
+    pub easing: Easing,
+    /// Which way the hands turn to get here
+    pub direction: Direction,
+    /// When each clocklet moves within the lifespan, if not all at once
+    timings: Option<Timings>,
 }
 
 impl ClockTarget {
-    pub fn from_time(time: &OffsetDateTime, lifespan: Lifespan) -> Self {
-        let mut me = Self::default();
-
-        let mut mins = time.minute();
-        let mut hours = time.hour();
-        debug!("got time {hours}:{mins}");
-        me.set_digit(&mins.into(), 3);
-        mins /= 10;
-        me.set_digit(&mins.into(), 2);
-        me.set_digit(&hours.into(), 1);
-        hours /= 10;
-        me.set_digit(&hours.into(), 0);
-        me.lifespan = lifespan;
+    /// Display `time` as `format` says, with as many digits as `resolution` calls for,
+    /// centred in a grid of `size`, and the AM/PM cue just after them if there's room
+    pub fn from_time(
+        time: &OffsetDateTime,
+        resolution: Resolution,
+        format: TimeFormat,
+        size: Size,
+        lifespan: Lifespan,
+    ) -> Self {
+        let (hours, mins, secs) = time.to_hms();
+        debug!("got time {hours}:{mins}:{secs}");
+        let (hours, pm) = match format.hours {
+            Hours::TwentyFour => (hours, false),
+            Hours::Twelve => ((hours + 11) % 12 + 1, hours >= 12),
+        };
+        let tens = match hours < 10 && !format.leading_zero {
+            true => Digit::BLANK,
+            false => Digit::from(hours / 10),
+        };
+        let rest = [hours, mins / 10, mins, secs / 10, secs].map(Digit::from);
+        let digits: Vec<_> = std::iter::once(tens).chain(rest).collect();
+        let digits = &digits[..resolution.digits()];
+        let mut me = Self::showing(digits, size, lifespan);
+
+        if pm {
+            // Same slots as `showing` and `set_digit` put the digits in
+            let first = me.digits().saturating_sub(digits.len()) / 2;
+            let col = (size.columns % Digit::COLUMNS) / 2 + (first + digits.len()) * Digit::COLUMNS;
+            let top = (size.rows - Digit::ROWS) / 2;
+            if let Some(cue) = me.clocklets.get_mut(col) {
+                cue[top..top + Digit::ROWS].fill(Clocklet::BLANK_PM);
+            }
+        }
+        me
+    }
+
+    /// Display `digits` side by side, centred in a grid of `size`
+    pub fn showing(digits: &[Digit], size: Size, lifespan: Lifespan) -> Self {
+        let mut me = Self::filled(size, Clocklet::BLANK, lifespan);
+        let first = me.digits().saturating_sub(digits.len()) / 2;
+        for (position, digit) in digits.iter().enumerate() {
+            me.set_digit(digit, first + position);
+        }
         me
     }
 
-    pub fn horizontals(lifespan: Lifespan) -> Self {
-        let clocklets = [[Clocklet::H; 3]; 8];
+    /// Display `pose`, columns of rows of clocklets of any size, centred in a grid of `size`,
+    /// with blanks around it if it's smaller and cropped if it's bigger
+    ///
+    /// ```
+    /// use klox::clock::{ClockTarget, Clocklet, Lifespan, Size};
+    ///
+    /// let pose = vec![vec![Clocklet::U], vec![Clocklet::D]];
+    /// let target = ClockTarget::centred(&pose, Size::new(8, 3), Lifespan::default());
+    /// assert_eq!(target.clocklets().len(), 8);
+    /// assert_eq!(target.clocklets()[3], [Clocklet::BLANK, Clocklet::U, Clocklet::BLANK]);
+    /// assert_eq!(target.clocklets()[4][1], Clocklet::D);
+    ///
+    /// let big = vec![vec![Clocklet::H; 5]; 12];
+    /// let target = ClockTarget::centred(&big, Size::new(8, 3), Lifespan::default());
+    /// assert_eq!(target.clocklets(), vec![vec![Clocklet::H; 3]; 8]);
+    /// ```
+    pub fn centred(pose: &[Vec<Clocklet>], size: Size, lifespan: Lifespan) -> Self {
+        let pose_size = Size::new(pose.len(), pose.first().map_or(0, Vec::len));
+        // Where the grid's corner falls on the pose, negative if the pose is smaller
+        let left = (pose_size.columns as isize - size.columns as isize) / 2;
+        let top = (pose_size.rows as isize - size.rows as isize) / 2;
+        let clocklets = size.grid(|col, row| {
+            let col = usize::try_from(col as isize + left).ok();
+            let row = usize::try_from(row as isize + top).ok();
+            col.zip(row)
+                .and_then(|(col, row)| pose.get(col)?.get(row).copied())
+                .unwrap_or(Clocklet::BLANK)
+        });
         Self {
             clocklets,
-            extra_turns: None,
             lifespan,
+            ..Default::default()
         }
     }
 
-    pub fn verticals(lifespan: Lifespan) -> Self {
-        let clocklets = [[Clocklet::V; 3]; 8];
+    fn filled(size: Size, clocklet: Clocklet, lifespan: Lifespan) -> Self {
         Self {
-            clocklets,
-            extra_turns: None,
+            clocklets: size.grid(|_, _| clocklet),
             lifespan,
+            ..Default::default()
         }
     }
 
-    pub fn slashies(lifespan: Lifespan) -> Self {
-        let clocklets = core::array::from_fn(|col| {
-            core::array::from_fn(|row| {
-                let p = (3 * col + row) as f64 / 24.0;
-                let q = 1.0 - p;
-                Clocklet::H * p + Clocklet::V * q
-            })
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_stagger(mut self, stagger: Stagger) -> Self {
+        self.timings = Some(stagger.timings(self.size()));
+        self
+    }
+
+    pub fn horizontals(size: Size, lifespan: Lifespan) -> Self {
+        Self::filled(size, Clocklet::H, lifespan)
+    }
+
+    pub fn verticals(size: Size, lifespan: Lifespan) -> Self {
+        Self::filled(size, Clocklet::V, lifespan)
+    }
+
+    pub fn slashies(size: Size, lifespan: Lifespan) -> Self {
+        let count = (size.rows * size.columns) as f64;
+        let clocklets = size.grid(|col, row| {
+            let p = (size.rows * col + row) as f64 / count;
+            let q = 1.0 - p;
+            Clocklet::H * p + Clocklet::V * q
         });
         Self {
             clocklets,
-            extra_turns: None,
             lifespan,
+            ..Default::default()
         }
     }
 
-    pub fn set_digit(&mut self, digit: &Digit, position: usize) {
-        let position = (position % 4) * 2;
+    fn size(&self) -> Size {
+        Size::new(
+            self.clocklets.len(),
+            self.clocklets.first().map_or(0, Vec::len),
+        )
+    }
 
-        let scope = &mut self.clocklets[position..(position + 2)];
+    /// The pose this target is heading for, as columns of rows of clocklets
+    pub fn clocklets(&self) -> &[Vec<Clocklet>] {
+        &self.clocklets
+    }
 
-        let clocklets = scope.iter_mut().flatten();
+    /// Number of digits that fit across this target
+    pub fn digits(&self) -> usize {
+        self.size().columns / Digit::COLUMNS
+    }
 
-        for (src, dst) in digit.into_iter().zip(clocklets) {
-            *dst = *src;
+    /// Draw `digit` in the `position`th digit-wide slot from the left, vertically centred
+    pub fn set_digit(&mut self, digit: &Digit, position: usize) {
+        let size = self.size();
+        // Centre the slots themselves, in case of an odd number of columns
+        let left = (size.columns % Digit::COLUMNS) / 2;
+        let col = left + (position % self.digits()) * Digit::COLUMNS;
+        let top = (size.rows - Digit::ROWS) / 2;
+
+        for (i, src) in digit.into_iter().enumerate() {
+            self.clocklets[col + i / Digit::ROWS][top + i % Digit::ROWS] = *src;
         }
     }
 
+    /// How far along its own transition a single clocklet is, after stagger and easing
+    pub fn clocklet_progress(&self, col: usize, row: usize) -> f64 {
+        let progress = self.progress();
+        let progress = match &self.timings {
+            Some(timings) => timings.progress(col, row, progress),
+            None => progress,
+        };
+        self.easing.apply(progress)
+    }
+
+    /// How far through its lifespan the target is, from 0 to 1
     pub fn progress(&self) -> f64 {
         match self.lifespan {
             Lifespan::Finished => 1.0,
@@ -366,19 +812,25 @@ impl ClockTarget {
         }
     }
 
-    pub fn random_millis(millis: u64) -> Self {
+    pub fn random_millis(size: Size, millis: u64) -> Self {
         Self {
-            clocklets: Default::default(),
-            extra_turns: Some([[3.0; 3]; 8]),
+            clocklets: size.grid(|_, _| Default::default()),
+            extra_turns: Some(size.grid(|_, _| 3.0)),
             lifespan: Lifespan::from_millis(millis),
+            ..Default::default()
         }
     }
 
-    /// Return updated target and extra turns
-    pub fn update(mut self, update: &Update) -> (Self, Option<[[f64; 3]; 8]>) {
-        self.lifespan = self.lifespan.update(update);
-        let extra_turns = self.extra_turns.take();
-        (self, extra_turns)
+    pub fn update(mut self, elapsed: Duration) -> Self {
+        self.lifespan = self.lifespan.update(elapsed);
+        self
+    }
+
+    fn extra_turns(&self, col: usize, row: usize) -> f64 {
+        self.extra_turns
+            .as_ref()
+            .and_then(|turns| turns.get(col)?.get(row).copied())
+            .unwrap_or_default()
     }
 
     pub fn is_finished(&self) -> bool {
@@ -389,9 +841,9 @@ impl ClockTarget {
     }
 }
 
-struct Clock {
-    /// 8 columns of 3 clocklets
-    /// 2 columns form 1 digit
+pub struct Clock {
+    /// Columns of rows of clocklets, 8x3 by default
+    /// 2 columns of 3 form 1 digit
     /// I didn't express in terms of digits, because
     /// general-purpose animations (i.e. not displaying numbers)
     /// will be really cumbersome
@@ -404,280 +856,960 @@ struct Clock {
     ///     clocklets: [[Clocklet; 3]; 2]
     /// }
     /// ```
-    clocklets: [[Clocklet; 3]; 8],
+    clocklets: Vec<Vec<Clocklet>>,
     /// Queue of animation targets to process
     targets: VecDeque<ClockTarget>,
+    /// Choreography to take more targets from once the queue runs dry
+    timeline: Option<Timeline>,
+    /// Around each clocklet
     padding: f32,
+    style: Style,
+    /// In place of the style's face color, for now
+    tint: Option<Color>,
 }
 
 impl Clock {
+    pub fn with_size(size: Size) -> Self {
+        Self {
+            padding: 8.0,
+            style: Style::default(),
+            tint: None,
+            clocklets: size.grid(|_, _| Default::default()),
+            targets: Default::default(),
+            timeline: None,
+        }
+    }
+
+    /// What the clocklets look like right now, tint included
+    pub fn style(&self) -> Style {
+        Style {
+            face: self.tint.unwrap_or(self.style.face),
+            ..self.style
+        }
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Draw the faces in `tint` rather than the style's color until it's set back to `None`
+    pub fn set_tint(&mut self, tint: Option<Color>) {
+        self.tint = tint;
+    }
+
+    /// Set the padding around each clocklet
+    pub fn set_padding(&mut self, padding: f32) {
+        self.padding = padding;
+    }
+
+    /// A clock just wide enough for `digits`, with the hands already showing them
+    pub fn showing(digits: &[Digit]) -> Self {
+        let clocklets = digits
+            .iter()
+            .flat_map(|digit| digit.0.chunks(Digit::ROWS).map(<[Clocklet]>::to_vec))
+            .collect();
+        Self {
+            clocklets,
+            ..Self::with_size(Size::new(0, Digit::ROWS))
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(
+            self.clocklets.len(),
+            self.clocklets.first().map_or(0, Vec::len),
+        )
+    }
+
+    /// Number of digits that fit across the clock
+    pub fn digits(&self) -> usize {
+        self.size().columns / Digit::COLUMNS
+    }
+
+    /// Change the grid size, keeping the hands where they are on the clocklets we keep.
+    /// Queued targets are for the old size, so they get dropped.
+    pub fn resize(&mut self, size: Size) {
+        self.clocklets = normalized(self.interpolated_clocklets());
+        self.clocklets.resize_with(size.columns, Default::default);
+        for col in self.clocklets.iter_mut() {
+            col.resize_with(size.rows, Default::default);
+        }
+        self.targets.clear();
+        self.timeline = None;
+    }
+
     pub fn push_target(&mut self, target: ClockTarget) {
         self.targets.push_back(target);
     }
 
     pub fn clobber_targets(&mut self, target: ClockTarget) {
         debug!("🔨 Clobbering clock with single target 🤷");
+        self.settle();
+        self.targets = [target].into();
+    }
+
+    /// Drop everything queued and play `timeline` from here
+    pub fn clobber_timeline(&mut self, timeline: Timeline) {
+        debug!("🔨 Clobbering clock with a timeline 🩰");
+        self.settle();
+        self.targets.clear();
+        self.timeline = Some(timeline);
+    }
+
+    /// Play `timeline` once everything already queued is done, replacing any timeline that's
+    /// already playing
+    pub fn push_timeline(&mut self, timeline: Timeline) {
+        self.timeline = Some(timeline);
+    }
+
+    /// Where the hands end up once everything queued has played, not counting any timeline
+    pub fn destination(&self) -> &[Vec<Clocklet>] {
+        self.targets
+            .back()
+            .map_or(&self.clocklets, |target| &target.clocklets)
+    }
+
+    /// Put the hands straight where they're heading, the timeline's destination if it has one,
+    /// dropping everything queued
+    pub fn skip_to_destination(&mut self) {
+        let destination = self
+            .timeline
+            .as_ref()
+            .and_then(Timeline::destination)
+            .unwrap_or_else(|| self.destination())
+            .to_vec();
+        self.clocklets = normalized(destination);
+        self.targets.clear();
+        self.timeline = None;
+    }
+
+    /// Stop any timeline, and freeze the hands where they are if they're moving, ready to
+    /// replace the queue
+    fn settle(&mut self) {
+        self.timeline = None;
         if let Some(ClockTarget {
             lifespan: Lifespan::Active { .. },
             ..
         }) = self.targets.front()
         {
-            self.clocklets = self.interpolated_clocklets();
+            self.clocklets = normalized(self.interpolated_clocklets());
         }
-        self.targets = [target].into();
     }
 
-    pub fn lerp(&self, target: &ClockTarget) -> [[Clocklet; 3]; 8] {
-        let progress = target.progress();
-
-        core::array::from_fn(|col| {
-            core::array::from_fn(|row| {
-                self.clocklets[col][row]
-                    + (target.clocklets[col][row] - self.clocklets[col][row]) * progress
+    pub fn lerp(&self, target: &ClockTarget) -> Vec<Vec<Clocklet>> {
+        self.clocklets
+            .iter()
+            .zip(&target.clocklets)
+            .enumerate()
+            .map(|(col, (from, to))| {
+                from.iter()
+                    .zip(to)
+                    .enumerate()
+                    .map(|(row, (&from, &to))| {
+                        let travel =
+                            from.travel(to, target.direction, target.extra_turns(col, row));
+                        from + travel * target.clocklet_progress(col, row)
+                    })
+                    .collect()
             })
-        })
+            .collect()
     }
 
     fn as_target(&self) -> ClockTarget {
         ClockTarget {
-            clocklets: self.clocklets,
-            extra_turns: None,
+            clocklets: self.clocklets.clone(),
             // lifespan: Lifespan::default(),
             lifespan: Lifespan::Pending(Duration::from_millis(1000)),
+            ..Default::default()
         }
     }
 
     /// Get a ClockTarget from Clock by replacing 6 clocklets with a given digit.
     /// Useful for working on digit definitions
-    pub fn target_digit(&mut self, digit: &Digit, position: usize) {
+    pub fn target_digit(&mut self, digit: &Digit, position: usize, transition: &Transition) {
         let mut target = self.as_target();
         target.set_digit(digit, position);
-        self.push_target(target);
+        self.push_target(transition.apply(target));
+    }
+
+    /// Each clocklet as it is right now, with the (padded) bounds to draw it in
+    pub fn layout(&self, bounds: Rect) -> Vec<(Rect, Clocklet)> {
+        let Size { columns, rows } = self.size();
+        let grid = bounds.grid_of(columns, rows);
+
+        let clocklets = self.interpolated_clocklets();
+
+        grid.into_iter()
+            .zip(clocklets)
+            .flat_map(|(rects, col)| rects.into_iter().zip(col))
+            .map(|(rect, clocklet)| (rect.pad(self.padding), clocklet))
+            .collect()
     }
 
-    fn interpolated_clocklets(&self) -> [[Clocklet; 3]; 8] {
+    /// Where the hands are right now, part way towards the front target.
+    ///
+    /// ```
+    /// use klox::clock::{Clock, ClockTarget, Clocklet, Lifespan};
+    /// use std::time::Duration;
+    ///
+    /// let at = Duration::from_millis;
+    ///
+    /// let mut clock = Clock::default();
+    /// clock.push_target(ClockTarget::verticals(clock.size(), Lifespan::default()));
+    /// clock.update(at(0));
+    /// clock.update(at(1));
+    /// assert_eq!(clock.interpolated_clocklets()[0][0], Clocklet::V);
+    ///
+    /// clock.push_target(ClockTarget::horizontals(clock.size(), Lifespan::from_millis(1000)));
+    /// clock.update(at(1000));
+    /// clock.update(at(1500));
+    /// assert_eq!(clock.interpolated_clocklets()[7][2], Clocklet::from_turns(0.125, 0.625));
+    ///
+    /// clock.update(at(2001));
+    /// assert_eq!(clock.interpolated_clocklets()[7][2], Clocklet::H);
+    /// ```
+    pub fn interpolated_clocklets(&self) -> Vec<Vec<Clocklet>> {
         // FIXME more implicit cloning
         self.targets
             .front()
             .map(|targets| self.lerp(targets))
-            .unwrap_or(self.clocklets)
+            .unwrap_or_else(|| self.clocklets.clone())
+    }
+
+    /// Move the animation on to `elapsed`, the time since it started
+    pub fn update(&mut self, elapsed: Duration) {
+        loop {
+            if self.targets.is_empty() {
+                let next = self
+                    .timeline
+                    .as_mut()
+                    .and_then(|timeline| timeline.next_target(&self.clocklets));
+                match next {
+                    Some(target) => self.targets.push_back(target),
+                    None => self.timeline = None,
+                }
+            }
+            let Some(target) = self.targets.pop_front() else {
+                break;
+            };
+            let updated = target.update(elapsed);
+            if updated.is_finished() {
+                self.clocklets = normalized(updated.clocklets);
+                continue;
+            }
+            self.targets.push_front(updated);
+            break;
+        }
     }
 }
 
 impl Default for Clock {
     fn default() -> Self {
-        Self {
-            padding: 8.0,
-            clocklets: Default::default(),
-            targets: Default::default(),
-        }
+        Self::with_size(Size::default())
     }
 }
 
 impl Drawable for Clock {
-    fn draw(&self, bounds: Rect, draw: &Draw) {
-        let grid: [[Rect; 3]; 8] = bounds.grid();
-
-        let clocklets = self.interpolated_clocklets();
-
-        for (i, col) in grid.into_iter().enumerate() {
-            for (j, rect) in col.into_iter().enumerate() {
-                clocklets[i][j].draw(rect.pad(self.padding), draw);
-            }
+    fn draw(&self, bounds: Rect, renderer: &mut dyn Renderer) {
+        let style = self.style();
+        for (rect, clocklet) in self.layout(bounds) {
+            clocklet.draw_styled(rect, &style, renderer);
         }
     }
+}
 
-    fn update(&mut self, update: &Update) {
-        while let Some(target) = self.targets.pop_front() {
-            let (updated, extra_turns) = target.update(update);
-            if updated.is_finished() {
-                self.clocklets = updated.clocklets;
-                continue;
-            }
-            if let Some(extra_turns) = extra_turns {
-                *self -= extra_turns;
-            }
-            self.targets.push_front(updated);
-            break;
-        }
+/// Take whole turns off every clocklet, so drift can't build up from one target to the next
+fn normalized(mut clocklets: Vec<Vec<Clocklet>>) -> Vec<Vec<Clocklet>> {
+    for clocklet in clocklets.iter_mut().flatten() {
+        *clocklet = clocklet.normalized();
     }
+    clocklets
 }
 
-impl SubAssign<[[f64; 3]; 8]> for Clock {
-    fn sub_assign(&mut self, rhs: [[f64; 3]; 8]) {
-        for (i, col) in self.clocklets.iter_mut().enumerate() {
-            for (j, clocklet) in col.iter_mut().enumerate() {
-                *clocklet -= rhs[i][j];
-            }
+/// Decides when the clock should be re-targeted to the wall-clock time.
+///
+/// Rather than arming on a particular second, we remember which minute (or second, depending on
+/// the [`Resolution`]) we last targeted and re-target whenever the one due to be on display
+/// (looking [`Self::LEAD_TIME_SECONDS`] ahead) differs from it. That covers startup, the regular
+/// minute change and wall-clock jumps (suspend/resume, NTP steps) alike.
+///
+/// ```
+/// use klox::clock::{Resolution, Size, TriggerTime, time_source::ManualTime, timezone::Zone};
+/// use std::time::Duration;
+/// use time::macros::datetime;
+///
+/// let time = ManualTime::new(datetime!(2025-06-01 12:00:30 UTC));
+/// let mut trigger = TriggerTime::new(time.clone(), Zone::utc());
+/// let size = Size::new(12, 3);
+///
+/// // Straight to the right time on startup
+/// assert!(trigger.trigger(size).is_some());
+/// assert!(trigger.trigger(size).is_none());
+///
+/// // Then again LEAD_TIME_SECONDS before the next minute
+/// time.step(Duration::from_secs(24));
+/// assert!(trigger.trigger(size).is_none());
+/// time.step(Duration::from_secs(1));
+/// assert!(trigger.trigger(size).is_some());
+/// assert!(trigger.trigger(size).is_none());
+///
+/// // And whenever the wall clock jumps
+/// time.jump(time::Duration::hours(3));
+/// assert!(trigger.trigger(size).is_some());
+///
+/// // Showing seconds, it's every second
+/// trigger.set_resolution(Resolution::Seconds);
+/// assert!(trigger.trigger(size).is_some());
+/// time.step(Duration::from_millis(1000));
+/// assert!(trigger.trigger(size).is_some());
+/// assert!(trigger.trigger(size).is_none());
+/// ```
+pub struct TriggerTime {
+    source: Box<dyn TimeSource>,
+    /// What to display the source's time in
+    zone: Zone,
+    resolution: Resolution,
+    format: TimeFormat,
+    /// How far ahead of each minute to set off for it
+    lead_time: Duration,
+    /// How long to take getting to the right time when we're not on the regular schedule
+    sync_time: Duration,
+    /// The minute or second (local time, truncated) of the last emitted target
+    targeted: Option<OffsetDateTime>,
+    /// Wall-clock and monotonic readings from the previous call, for jump detection
+    last_seen: Option<(OffsetDateTime, Duration)>,
+}
+
+impl TriggerTime {
+    /// The default lead time
+    pub const LEAD_TIME_SECONDS: u8 = 5;
+    /// Lead time when showing seconds, so the hands are still for the rest of each second
+    const SECONDS_LEAD_TIME_MILLIS: u64 = 400;
+    /// The default sync time
+    pub const SYNC_MILLIS: u64 = 1500;
+    /// How far the wall clock may drift from the monotonic clock between calls before we
+    /// consider it to have jumped
+    const JUMP_TOLERANCE: Duration = Duration::from_secs(2);
+
+    pub fn new(source: impl TimeSource + 'static, zone: Zone) -> Self {
+        Self::from_boxed(Box::new(source), zone)
+    }
+
+    pub fn from_boxed(source: Box<dyn TimeSource>, zone: Zone) -> Self {
+        Self {
+            source,
+            zone,
+            resolution: Resolution::default(),
+            format: TimeFormat::default(),
+            lead_time: Duration::from_secs(Self::LEAD_TIME_SECONDS as u64),
+            sync_time: Duration::from_millis(Self::SYNC_MILLIS),
+            targeted: None,
+            last_seen: None,
         }
     }
-}
 
-#[derive(Default)]
-struct TriggerTime(bool);
+    pub fn zone(&self) -> &Zone {
+        &self.zone
+    }
 
-impl TriggerTime {
-    const LEAD_TIME_SECONDS: u8 = 5;
-    const TRIGGER_TIME_SECONDS: u8 = 60 - Self::LEAD_TIME_SECONDS;
+    /// Display the time in `zone` from now on, resyncing on the next trigger
+    pub fn set_zone(&mut self, zone: Zone) {
+        self.zone = zone;
+        self.targeted = None;
+    }
+
+    /// Set how far ahead of each minute to set off for it, and how long to take getting to the
+    /// right time off schedule (capped at the lead time)
+    pub fn set_timings(&mut self, lead_time: Duration, sync_time: Duration) {
+        self.lead_time = lead_time;
+        self.sync_time = sync_time;
+    }
 
-    pub fn trigger(&mut self) -> Option<ClockTarget> {
-        // Get local time
-        let now_utc = OffsetDateTime::now_utc();
-        let local_offset = UtcOffset::current_local_offset().unwrap();
-        let now_local = now_utc.to_offset(local_offset);
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
 
-        // Get the current seconds within the minute
-        let seconds = now_local.second();
+    /// The time now, whatever the zone
+    pub fn now(&self) -> OffsetDateTime {
+        self.source.now()
+    }
 
-        // We have already triggered, check if we should re-arm
-        if self.0 && seconds < Self::TRIGGER_TIME_SECONDS {
-            self.0 = false;
-        } else if !self.0 && seconds >= Self::TRIGGER_TIME_SECONDS {
-            self.0 = true;
+    /// The time now, in [`Self::zone`]
+    pub fn local_now(&self) -> OffsetDateTime {
+        self.zone.to_local(self.source.now())
+    }
 
-            let target = ClockTarget::from_time(
-                &(now_local + Duration::from_secs(Self::LEAD_TIME_SECONDS as u64)),
-                Lifespan::from_millis(Self::LEAD_TIME_SECONDS as u64 * 1000),
-            );
-            return Some(target);
+    /// Forget what's showing, so the next trigger puts the time back up however long it is
+    /// until it's due
+    pub fn resync(&mut self) {
+        self.targeted = None;
+    }
+
+    /// Switch between minutes and seconds, resyncing on the next trigger
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+        self.targeted = None;
+    }
+
+    pub fn format(&self) -> TimeFormat {
+        self.format
+    }
+
+    /// Write the time out as `format` says from now on, resyncing on the next trigger
+    pub fn set_format(&mut self, format: TimeFormat) {
+        self.format = format;
+        self.targeted = None;
+    }
+
+    fn lead(&self) -> Duration {
+        match self.resolution {
+            Resolution::Minutes => self.lead_time,
+            Resolution::Seconds => Duration::from_millis(Self::SECONDS_LEAD_TIME_MILLIS),
         }
+    }
+
+    /// Returns a target for a clock of the given `size`, if it's time for one
+    pub fn trigger(&mut self, size: Size) -> Option<ClockTarget> {
+        let now = self.source.now();
+        let now_monotonic = self.source.monotonic();
 
-        None
+        if self.jumped(now, now_monotonic) {
+            // Forget what we're showing so we resync below, whatever minute it is
+            self.targeted = None;
+        }
+        self.last_seen = Some((now, now_monotonic));
+
+        // Convert after adding the lead time, the offset might be about to change for DST
+        let due = self
+            .resolution
+            .truncate(self.zone.to_local(now + self.lead()));
+        if self.targeted == Some(due) {
+            return None;
+        }
+
+        // On schedule this is the lead time; otherwise we're late, so get there quickly
+        let millis = match (
+            self.targeted,
+            u64::try_from((due - now).whole_milliseconds()),
+        ) {
+            (Some(_), Ok(millis)) if millis > 0 => millis,
+            _ => self.sync_time.min(self.lead()).as_millis() as u64,
+        };
+        self.targeted = Some(due);
+
+        Some(ClockTarget::from_time(
+            &due,
+            self.resolution,
+            self.format,
+            size,
+            Lifespan::from_millis(millis),
+        ))
+    }
+
+    /// Returns true if the wall clock has moved differently from the monotonic clock since the
+    /// last call
+    fn jumped(&self, now: OffsetDateTime, monotonic: Duration) -> bool {
+        let Some((last, last_monotonic)) = self.last_seen else {
+            return false;
+        };
+        let wall = now - last;
+        let mono = monotonic.saturating_sub(last_monotonic);
+        let drift = (wall - mono).unsigned_abs();
+        if drift > Self::JUMP_TOLERANCE {
+            debug!("⏰ wall clock jumped by {wall} over {mono:?}, resyncing");
+            return true;
+        }
+        false
     }
 }
 
 pub struct Model {
     padding: f32,
+    /// Grid size to use, as long as the time fits
+    size: Size,
     clock: Clock,
+    /// How each kind of target moves
+    transitions: Transitions,
+    /// Played on the way to each new minute, instead of going straight there
+    choreography: Option<Timeline>,
     debug_digit: usize,
     trigger_time: TriggerTime,
-    pub background: wgpu::Texture,
-    pub background_width: f32,
-    pub background_height: f32,
+    /// Taking over from [`Self::trigger_time`] every now and then
+    trigger_date: TriggerDate,
+    /// Which zone [`Self::trigger_time`] tells the time in
+    trigger_zone: TriggerZone,
+    /// How long [`Pattern::Scramble`] takes
+    scramble_millis: u64,
+    /// How long the geometric patterns take
+    pattern_millis: u64,
+    /// Held still instead of the time, see [`Self::freeze`]
+    frozen: Option<Vec<Digit>>,
+    /// Shown instead of the time, see [`Self::count_down`]
+    countdown: Option<Countdown>,
+    /// Shown instead of the time, see [`Self::time_with_stopwatch`]
+    stopwatch: Option<Stopwatch>,
+    alarms: Alarms,
+    /// Flashed on the faces while an alarm's going off
+    alarm_tint: Color,
 }
 
 impl Model {
     pub fn scramble_millis(&mut self, millis: u64) {
-        self.clock.push_target(ClockTarget::random_millis(millis));
+        let target = ClockTarget::random_millis(self.clock.size(), millis);
+        self.clock
+            .push_target(self.transitions.scramble.apply(target));
+    }
+
+    /// Queue up `pattern` to play after whatever's already queued
+    pub fn play(&mut self, pattern: Pattern) {
+        let size = self.clock.size();
+        let lifespan = Lifespan::from_millis(self.pattern_millis);
+        let target = match pattern {
+            Pattern::Horizontals => ClockTarget::horizontals(size, lifespan),
+            Pattern::Verticals => ClockTarget::verticals(size, lifespan),
+            Pattern::Slashies => ClockTarget::slashies(size, lifespan),
+            Pattern::Scramble => return self.scramble_millis(self.scramble_millis),
+            Pattern::MinuteChange => {
+                // Come back to wherever the queue leaves the hands
+                let back = ClockTarget {
+                    clocklets: self.clock.destination().to_vec(),
+                    lifespan: Lifespan::Finished,
+                    ..Default::default()
+                };
+                self.clock
+                    .push_timeline(Timeline::minute_change().towards(back));
+                return;
+            }
+        };
+        self.clock
+            .push_target(self.transitions.pattern.apply(target));
+    }
+
+    /// Switch between HH:MM and HH:MM:SS, resizing the clock if it's too small
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        let mut size = self.size.fit(resolution, self.trigger_time.format());
+        if let Some(digits) = &self.frozen {
+            size.columns = size.columns.max(digits.len() * Digit::COLUMNS);
+        }
+        self.clock.resize(size);
+        self.trigger_time.set_resolution(resolution);
+        self.debug_digit %= self.clock.digits();
+        self.redraw();
+    }
+
+    /// Put whatever's showing instead of the time back up, e.g. after a resize
+    fn redraw(&mut self) {
+        self.show_frozen();
+        if let Some(countdown) = &mut self.countdown {
+            countdown.redraw();
+        }
+        if let Some(stopwatch) = &mut self.stopwatch {
+            stopwatch.redraw();
+        }
+    }
+
+    /// Stop telling the time and hold `digits` still instead, centred like the time is, with
+    /// the clock widened if they don't fit
+    ///
+    /// ```
+    /// use klox::clock::{Digit, Model, time_source::ManualTime, timezone::Zone};
+    /// use klox::geom::Rect;
+    /// use std::time::Duration;
+    /// use time::macros::datetime;
+    ///
+    /// let time = ManualTime::new(datetime!(2025-06-01 12:34:56 UTC));
+    /// let mut model = Model::with_time(Box::new(time), Zone::utc());
+    /// let digits: Vec<_> = "12 345".chars().map(|c| Digit::try_from(c).unwrap()).collect();
+    /// model.freeze(&digits);
+    /// model.update(Duration::from_secs(10));
+    ///
+    /// // Six digits of 2x3 clocklets, where HH:MM only needs four
+    /// assert_eq!(model.layout(Rect::from_w_h(1200.0, 300.0)).len(), 36);
+    /// ```
+    pub fn freeze(&mut self, digits: &[Digit]) {
+        self.frozen = Some(digits.to_vec());
+        self.set_resolution(self.trigger_time.resolution());
+    }
+
+    fn show_frozen(&mut self) {
+        let Some(digits) = &self.frozen else {
+            return;
+        };
+        let lifespan = Lifespan::Pending(self.trigger_time.sync_time);
+        let target = ClockTarget::showing(digits, self.clock.size(), lifespan);
+        self.clock
+            .clobber_targets(self.transitions.digit.apply(target));
+    }
+
+    /// Stop telling the time and count down from `duration` instead, starting paused
+    pub fn count_down(&mut self, duration: Duration) {
+        self.countdown = Some(Countdown::new(duration));
+    }
+
+    /// The countdown, if there is one
+    pub fn countdown(&self) -> Option<&Countdown> {
+        self.countdown.as_ref()
+    }
+
+    /// Stop telling the time and show a stopwatch instead, starting stopped
+    pub fn time_with_stopwatch(&mut self) {
+        self.stopwatch = Some(Stopwatch::default());
+    }
+
+    /// The stopwatch, if there is one
+    pub fn stopwatch(&self) -> Option<&Stopwatch> {
+        self.stopwatch.as_ref()
+    }
+
+    /// Start or pause the countdown or the stopwatch, whichever there is
+    pub fn start_stop(&mut self) {
+        if let Some(countdown) = &mut self.countdown {
+            countdown.toggle();
+        }
+        if let Some(stopwatch) = &mut self.stopwatch {
+            stopwatch.toggle();
+        }
     }
 
-    fn new(app: &App) -> Self {
-        // Put your PNG in ./assets/background.png
-        let assets = app.assets_path().expect("assets dir");
-        let img_path = assets.join("background.png");
+    /// Put the countdown or the stopwatch back to the start, whichever there is
+    pub fn reset_timer(&mut self) {
+        if let Some(countdown) = &mut self.countdown {
+            countdown.reset();
+        }
+        if let Some(stopwatch) = &mut self.stopwatch {
+            stopwatch.reset();
+        }
+    }
+
+    /// Hold what the stopwatch is showing for a while, if there is one
+    pub fn lap(&mut self) {
+        if let Some(stopwatch) = &mut self.stopwatch {
+            stopwatch.lap();
+        }
+    }
 
-        // Load once via image to get dimensions, then create a texture.
-        let img = nannou::image::open(&img_path).expect("open background.png");
-        let (w, h) = img.dimensions();
-        let background = wgpu::Texture::from_image(app, &img);
+    /// Give the countdown another minute, if there is one
+    pub fn extend_countdown(&mut self) {
+        if let Some(countdown) = &mut self.countdown {
+            countdown.add_minute();
+        }
+    }
+
+    /// A few quick scrambles, then settle on 00:00
+    fn finish_countdown(&mut self) {
+        let size = self.clock.size();
+        let scramble = || ClockTarget::random_millis(size, Countdown::FINISH_SCRAMBLE_MILLIS);
+        self.clock
+            .clobber_targets(self.transitions.scramble.apply(scramble()));
+        for _ in 1..Countdown::FINISH_SCRAMBLES {
+            self.clock
+                .push_target(self.transitions.scramble.apply(scramble()));
+        }
+        let lifespan = Lifespan::from_millis(self.scramble_millis);
+        let zero = ClockTarget::showing(&[Digit::ZERO; 4], size, lifespan);
+        self.clock.push_target(self.transitions.time.apply(zero));
+    }
 
+    /// Flip between HH:MM and HH:MM:SS
+    pub fn toggle_resolution(&mut self) {
+        let resolution = match self.trigger_time.resolution() {
+            Resolution::Minutes => Resolution::Seconds,
+            Resolution::Seconds => Resolution::Minutes,
+        };
+        self.set_resolution(resolution);
+    }
+
+    /// Skip whatever's moving the hands and show where it's heading straight away, e.g. so a
+    /// render starts on the time rather than wherever the hands started out
+    pub fn skip_to_destination(&mut self) {
+        self.clock.skip_to_destination();
+    }
+
+    /// Turn the minute change choreography on or off
+    pub fn toggle_choreography(&mut self) {
+        self.choreography = match self.choreography {
+            Some(_) => None,
+            None => Some(Timeline::minute_change()),
+        };
+    }
+
+    /// Push a target replacing the next debug digit position with `digit`
+    pub fn push_debug_digit(&mut self, digit: &Digit) {
+        self.clock
+            .target_digit(digit, self.debug_digit, &self.transitions.digit);
+        self.debug_digit = (self.debug_digit + 1) % self.clock.digits();
+    }
+
+    /// A model telling the time from `source` in `zone`
+    pub fn with_time(source: Box<dyn TimeSource>, zone: Zone) -> Self {
+        let size = Size::default();
         Self {
             padding: 10.0,
-            clock: Default::default(),
+            size,
+            clock: Clock::with_size(size),
+            transitions: Default::default(),
+            choreography: None,
             debug_digit: 0,
-            background,
-            background_width: w as f32,
-            background_height: h as f32,
-            trigger_time: Default::default(),
+            trigger_zone: TriggerZone::new(zone.clone()),
+            trigger_time: TriggerTime::from_boxed(source, zone),
+            trigger_date: TriggerDate::new(DateOrder::default()),
+            scramble_millis: 3000,
+            pattern_millis: 5000,
+            frozen: None,
+            countdown: None,
+            stopwatch: None,
+            alarms: Alarms::default(),
+            alarm_tint: Color::WHITE,
         }
     }
-}
 
-impl Drawable for Model {
-    fn draw(&self, bounds: Rect, draw: &Draw) {
-        let (w, h) = bounds.w_h();
-        let bounds = Rect::from_w_h(clamp_max(w, h * 8.0 / 3.0), clamp_max(h, w * 3.0 / 8.0));
-        let bounds = bounds.pad(self.padding);
-        self.clock.draw(bounds, draw);
+    /// A model telling the time from `KLOX_TIME` (see [`time_source::from_env`]), set up as
+    /// `config` says
+    pub fn from_config(config: &Config) -> Self {
+        let zone = config.zone();
+        let mut model = Self::with_time(time_source::from_env(&zone), zone);
+        model.configure(config);
+        model
     }
 
-    fn update(&mut self, update: &Update) {
-        self.clock.update(update);
+    /// Look and move as `config` says from now on. Whatever's animating carries on, unless the
+    /// grid has to change size.
+    pub fn configure(&mut self, config: &Config) {
+        let layout = &config.layout;
+        self.padding = layout.padding;
+        self.clock.set_padding(layout.clocklet_padding);
+        self.clock.set_style(Style {
+            face: config.colors.face,
+            hand: config.colors.hand,
+            hand_length: layout.hand_length,
+            hand_weight: layout.hand_weight,
+        });
+
+        let timings = &config.timings;
+        self.scramble_millis = timings.scramble_millis;
+        self.pattern_millis = timings.pattern_millis;
+        self.trigger_time.set_timings(
+            Duration::from_millis(timings.lead_millis),
+            Duration::from_millis(timings.sync_millis),
+        );
+
+        self.transitions = config.transitions.transitions();
+        if config.transitions.choreography != self.choreography.is_some() {
+            self.toggle_choreography();
+        }
+
+        let zone = config.zone();
+        if zone.name() != self.trigger_zone.home().name() {
+            self.trigger_zone.set_home(zone);
+        }
+        let world = &config.world;
+        self.trigger_zone.set_zones(
+            world.zones.clone(),
+            Duration::from_secs(world.every_seconds),
+        );
+        if !self.alarms.is_ringing() {
+            self.clock.set_tint(self.trigger_zone.tint());
+        }
+        let date = &config.date;
+        self.trigger_date.set_order(date.order);
+        self.trigger_date.set_schedule(
+            (date.every_seconds > 0).then(|| Duration::from_secs(date.every_seconds)),
+            Duration::from_secs(date.hold_seconds),
+        );
+        self.trigger_date
+            .set_sync_time(Duration::from_millis(timings.sync_millis));
+
+        let alarms = &config.alarms;
+        self.alarms.set(
+            alarms.at.clone(),
+            Duration::from_secs(alarms.snooze_minutes * 60),
+        );
+        self.alarm_tint = alarms.tint;
+
+        let format = config.time.format();
+        let reformat = format != self.trigger_time.format();
+        if reformat {
+            self.trigger_time.set_format(format);
+        }
+
+        let resolution = match config.time.seconds {
+            true => Resolution::Seconds,
+            false => Resolution::Minutes,
+        };
+        // Back to the default without a grid, so a reload ends up where a fresh start would
+        let size = layout.grid.unwrap_or_default();
+        // The AM/PM cue might need room, or not any more
+        if size != self.size || resolution != self.trigger_time.resolution() || reformat {
+            self.size = size;
+            self.set_resolution(resolution);
+        }
     }
-}
 
-pub fn app() -> nannou::app::Builder<Model> {
-    nannou::app(model).event(event)
-}
+    /// What the clocklets look like right now
+    pub fn style(&self) -> Style {
+        self.clock.style()
+    }
 
-fn model(app: &App) -> Model {
-    Model::new(app)
-}
+    /// Whether an alarm's going off. Alarms go off whatever's on the clock, a countdown
+    /// included.
+    ///
+    /// ```
+    /// use klox::clock::{Model, time_source::ManualTime, timezone::Zone};
+    /// use klox::config::Config;
+    /// use std::time::Duration;
+    /// use time::macros::datetime;
+    ///
+    /// let time = ManualTime::new(datetime!(2025-06-01 11:59:30 UTC));
+    /// let mut model = Model::with_time(Box::new(time.clone()), Zone::utc());
+    /// model.configure(&Config::from_toml("[alarms]\nat = [\"12:00\"]").unwrap());
+    /// model.count_down(Duration::from_secs(25 * 60));
+    /// model.update(Duration::ZERO);
+    ///
+    /// time.step(Duration::from_secs(30));
+    /// model.update(Duration::from_secs(30));
+    /// assert!(model.is_ringing());
+    /// model.dismiss_alarm();
+    /// assert!(!model.is_ringing());
+    /// ```
+    pub fn is_ringing(&self) -> bool {
+        self.alarms.is_ringing()
+    }
 
-fn event(app: &App, model: &mut Model, event: Event) {
-    match event {
-        Event::WindowEvent {
-            simple: Some(WindowEvent::KeyPressed(key)),
-            ..
-        } => match key {
-            Key::Q => {
-                app.quit();
-            }
-            Key::R => {
-                model.scramble_millis(3000);
-            }
-            Key::Space => {
-                model.clock.target_digit(&Digit::BLANK, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key0 => {
-                model.clock.target_digit(&Digit::ZERO, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key1 => {
-                model.clock.target_digit(&Digit::ONE, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key2 => {
-                model.clock.target_digit(&Digit::TWO, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key3 => {
-                model.clock.target_digit(&Digit::THREE, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key4 => {
-                model.clock.target_digit(&Digit::FOUR, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key5 => {
-                model.clock.target_digit(&Digit::FIVE, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key6 => {
-                model.clock.target_digit(&Digit::SIX, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key7 => {
-                model.clock.target_digit(&Digit::SEVEN, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key8 => {
-                model.clock.target_digit(&Digit::EIGHT, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
-            }
-            Key::Key9 => {
-                model.clock.target_digit(&Digit::NINE, model.debug_digit);
-                model.debug_digit = (model.debug_digit + 1) % 4;
+    /// Stop the alarm that's going off, if there is one, and go back to the time
+    pub fn dismiss_alarm(&mut self) {
+        if self.alarms.is_ringing() {
+            self.alarms.dismiss();
+            self.stop_ringing();
+        }
+    }
+
+    /// Stop the alarm that's going off, if there is one, until it's snoozed long enough
+    pub fn snooze_alarm(&mut self) {
+        if self.alarms.is_ringing() {
+            self.alarms.snooze();
+            self.stop_ringing();
+        }
+    }
+
+    fn stop_ringing(&mut self) {
+        self.clock.set_tint(self.trigger_zone.tint());
+        // Taking over from the wake-up timeline straight away
+        self.trigger_time.resync();
+        self.redraw();
+    }
+
+    /// Put the date up for a while, then go back to the time
+    pub fn show_date(&mut self) {
+        self.trigger_date.show();
+    }
+
+    /// Re-target the clock if it's time to, then move the animation on to `elapsed`, the time
+    /// since it started
+    pub fn update(&mut self, elapsed: Duration) {
+        if self.ring_alarms(elapsed) {
+            // The wake-up timeline has the clock to itself
+        } else if self.countdown.is_some() {
+            self.update_countdown(elapsed);
+        } else if let Some(stopwatch) = &mut self.stopwatch {
+            if let Some(target) = stopwatch.trigger(elapsed, self.clock.size()) {
+                self.clock
+                    .clobber_targets(self.transitions.digit.apply(target));
             }
-            Key::Minus => {
-                model
-                    .clock
-                    .push_target(ClockTarget::horizontals(Lifespan::from_millis(5000)));
+        } else if self.frozen.is_none() {
+            self.tell_time();
+        }
+        self.clock.update(elapsed);
+    }
+
+    /// Check the alarms whatever's on the clock, taking it over with the wake-up timeline and a
+    /// flashing tint when one goes off. Returns whether one's going off.
+    fn ring_alarms(&mut self, elapsed: Duration) -> bool {
+        // Alarms go off at home, wherever the time's being told
+        let now = self.trigger_zone.home().to_local(self.trigger_time.now());
+        if self.alarms.check(now) {
+            self.clock.clobber_timeline(Timeline::wake_up());
+        }
+        if !self.alarms.is_ringing() {
+            return false;
+        }
+        // Flashing between the alarm's tint and whatever the faces would be otherwise
+        let on = (elapsed.as_millis() / Alarms::FLASH_MILLIS).is_multiple_of(2);
+        let tint = match on {
+            true => Some(self.alarm_tint),
+            false => self.trigger_zone.tint(),
+        };
+        self.clock.set_tint(tint);
+        true
+    }
+
+    /// Re-target the clock for the date or the time, whichever's due
+    fn tell_time(&mut self) {
+        let now = self.trigger_time.now();
+        if let Some(zone) = self.trigger_zone.trigger(now) {
+            self.trigger_time.set_zone(zone.clone());
+            self.clock.set_tint(self.trigger_zone.tint());
+        }
+        let now = self.trigger_time.local_now();
+
+        match self.trigger_date.trigger(now, self.clock.size()) {
+            Some(DateEvent::Show(target)) => {
+                self.clock
+                    .clobber_targets(self.transitions.time.apply(target));
             }
-            Key::Backslash => {
-                model
-                    .clock
-                    .push_target(ClockTarget::slashies(Lifespan::from_millis(5000)));
+            // Straight back to the time, rather than waiting for the next one
+            Some(DateEvent::Hide) => self.trigger_time.resync(),
+            None => {}
+        }
+        // The date has the clock to itself while it's up
+        if self.trigger_date.is_showing() {
+            return;
+        }
+
+        if let Some(time_target) = self.trigger_time.trigger(self.clock.size()) {
+            let time_target = self.transitions.time.apply(time_target);
+            match &self.choreography {
+                // Far too much going on to do every second
+                Some(timeline) if self.trigger_time.resolution() == Resolution::Minutes => {
+                    self.clock
+                        .clobber_timeline(timeline.clone().towards(time_target));
+                }
+                _ => self.clock.clobber_targets(time_target),
             }
-            _ => {}
-        },
-        Event::Update(ref update) => {
-            if let Some(time_target) = model.trigger_time.trigger() {
-                model.clock.clobber_targets(time_target);
+        }
+    }
+
+    /// Re-target the clock for what's left of the countdown, if that's changed
+    fn update_countdown(&mut self, elapsed: Duration) {
+        let Some(countdown) = &mut self.countdown else {
+            return;
+        };
+        match countdown.trigger(elapsed, self.clock.size()) {
+            Some(CountdownEvent::Show(target)) => {
+                self.clock
+                    .clobber_targets(self.transitions.digit.apply(target));
             }
-            model.update(update);
+            Some(CountdownEvent::Finished) => self.finish_countdown(),
+            None => {}
         }
-        _ => {}
+    }
+
+    /// The part of `bounds` the clock itself gets drawn in
+    pub fn clock_bounds(&self, bounds: Rect) -> Rect {
+        let (w, h) = bounds.w_h();
+        let aspect = self.clock.size().aspect();
+        let bounds = Rect::from_w_h(w.min(h * aspect), h.min(w / aspect));
+        bounds.pad(self.padding)
+    }
+
+    /// Each clocklet as it is right now, with the bounds to draw it in when the whole model is
+    /// drawn in `bounds`
+    pub fn layout(&self, bounds: Rect) -> Vec<(Rect, Clocklet)> {
+        self.clock.layout(self.clock_bounds(bounds))
+    }
+}
+
+impl Drawable for Model {
+    fn draw(&self, bounds: Rect, renderer: &mut dyn Renderer) {
+        self.clock.draw(self.clock_bounds(bounds), renderer);
     }
 }
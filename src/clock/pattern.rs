@@ -0,0 +1,71 @@
+//! Whole-clock animations with names, for playing on demand rather than to tell the time.
+
+use std::{fmt, str::FromStr};
+
+/// Something to play on the clock, see [`super::Model::play`].
+///
+/// ```
+/// use klox::clock::pattern::Pattern;
+///
+/// for pattern in Pattern::ALL {
+///     assert_eq!(pattern.name().parse::<Pattern>(), Ok(pattern));
+/// }
+/// assert!("zigzags".parse::<Pattern>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// Every hand pointing left and right
+    Horizontals,
+    /// Every hand pointing up and down
+    Verticals,
+    /// Diagonals sweeping across the clock
+    Slashies,
+    /// Every hand somewhere random
+    Scramble,
+    /// The choreography played between minutes
+    MinuteChange,
+}
+
+impl Pattern {
+    pub const ALL: [Self; 5] = [
+        Self::Horizontals,
+        Self::Verticals,
+        Self::Slashies,
+        Self::Scramble,
+        Self::MinuteChange,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Horizontals => "horizontals",
+            Self::Verticals => "verticals",
+            Self::Slashies => "slashies",
+            Self::Scramble => "scramble",
+            Self::MinuteChange => "minute-change",
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|pattern| pattern.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|pattern| pattern.name()).collect();
+                format!(
+                    "unknown pattern {s:?}, expected one of {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
@@ -0,0 +1,62 @@
+//! Showing a running duration on the clock, for the countdown and the stopwatch.
+
+use super::{ClockTarget, Digit, Lifespan, Size};
+
+/// `secs` as MM:SS for the first hour and HH:MM after that, rounding the minutes up if
+/// `round_up`, so a countdown never looks further along than it is
+///
+/// ```
+/// use klox::clock::{Digit, readout::digits};
+///
+/// let text = |secs, round_up| digits(secs, round_up).map(|digit| {
+///     (0..10).find(|&n| Digit::from(n) == digit).unwrap()
+/// });
+/// assert_eq!(text(25 * 60, false), [2, 5, 0, 0]);
+/// assert_eq!(text(3599, false), [5, 9, 5, 9]);
+/// assert_eq!(text(3600 + 90, false), [0, 1, 0, 1]);
+/// assert_eq!(text(3600 + 90, true), [0, 1, 0, 2]);
+/// ```
+pub fn digits(secs: u64, round_up: bool) -> [Digit; 4] {
+    let (high, low) = match secs < 3600 {
+        true => (secs / 60, secs % 60),
+        false => {
+            let mins = match round_up {
+                true => secs.div_ceil(60),
+                false => secs / 60,
+            };
+            (mins / 60, mins % 60)
+        }
+    };
+    // Hours past 99 only show the last two digits, which is fair enough
+    [high / 10, high, low / 10, low].map(|n| Digit::from((n % 10) as u8))
+}
+
+/// What's on the clock, so each new value only gets a target when it's different
+#[derive(Clone, Debug, Default)]
+pub struct Readout {
+    shown: Option<[Digit; 4]>,
+}
+
+impl Readout {
+    /// How long moving to each new value takes
+    pub const STEP_MILLIS: u64 = 300;
+
+    /// A target showing `digits` on a clock of the given `size`, unless they're already up
+    pub fn show(&mut self, digits: [Digit; 4], size: Size) -> Option<ClockTarget> {
+        if self.shown.replace(digits) == Some(digits) {
+            return None;
+        }
+        let lifespan = Lifespan::from_millis(Self::STEP_MILLIS);
+        Some(ClockTarget::showing(&digits, size, lifespan))
+    }
+
+    /// Take it that `digits` are up, without a target, when something else puts them there
+    pub fn assume(&mut self, digits: [Digit; 4]) {
+        self.shown = Some(digits);
+    }
+
+    /// Show it all again next time, e.g. after the clock's been resized
+    pub fn redraw(&mut self) {
+        self.shown = None;
+    }
+}
@@ -0,0 +1,150 @@
+//! Per-clocklet timing within a [`super::ClockTarget`]'s lifespan, so transitions can cascade
+//! across the clock rather than every hand moving in lockstep.
+
+use rand::random;
+
+use super::{Digit, Size};
+
+/// When a single clocklet moves, as fractions of the whole target's lifespan
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timing {
+    pub delay: f64,
+    pub duration: f64,
+}
+
+impl Timing {
+    /// Map the target's overall progress to this clocklet's progress
+    pub fn progress(self, progress: f64) -> f64 {
+        if self.duration <= 0.0 {
+            return if progress >= self.delay { 1.0 } else { 0.0 };
+        }
+        ((progress - self.delay) / self.duration).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            delay: 0.0,
+            duration: 1.0,
+        }
+    }
+}
+
+/// A [`Timing`] for each clocklet, as columns of rows like the clocklets themselves.
+///
+/// Every timing finishes within the lifespan, so a target is done exactly when its
+/// lifespan is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timings(Vec<Vec<Timing>>);
+
+impl Timings {
+    pub fn progress(&self, col: usize, row: usize, progress: f64) -> f64 {
+        self.0
+            .get(col)
+            .and_then(|rows| rows.get(row))
+            .copied()
+            .unwrap_or_default()
+            .progress(progress)
+    }
+}
+
+/// Built-in ways of spreading clocklets' start times across a transition.
+///
+/// Each carries a `spread` between 0 and 1: the fraction of the lifespan over which start
+/// times are spread. Every clocklet gets the remaining `1 - spread` to make its move, so 0 is
+/// lockstep and 1 has each clocklet move in an instant, one after another.
+///
+/// ```
+/// use klox::clock::Size;
+/// use klox::clock::stagger::Stagger;
+///
+/// let timings = Stagger::Sweep(0.5).timings(Size::new(8, 3));
+///
+/// // Halfway through, the left column has arrived and the right one is setting off
+/// assert_eq!(timings.progress(0, 0, 0.5), 1.0);
+/// assert_eq!(timings.progress(7, 0, 0.5), 0.0);
+/// assert_eq!(timings.progress(7, 2, 1.0), 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stagger {
+    /// Left to right
+    Sweep(f64),
+    /// Out from the centre
+    Radial(f64),
+    /// Any old order
+    Random(f64),
+    /// Left to right, a digit (pair of columns) at a time
+    Digits(f64),
+}
+
+impl Stagger {
+    pub fn spread(self) -> f64 {
+        match self {
+            Self::Sweep(spread)
+            | Self::Radial(spread)
+            | Self::Random(spread)
+            | Self::Digits(spread) => spread.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Work out timings for a clock of the given size
+    pub fn timings(self, size: Size) -> Timings {
+        let last_col = size.columns.saturating_sub(1).max(1) as f64;
+        let (mid_col, mid_row) = (
+            size.columns.saturating_sub(1) as f64 / 2.0,
+            size.rows.saturating_sub(1) as f64 / 2.0,
+        );
+        let max_distance = mid_col.hypot(mid_row).max(f64::EPSILON);
+        let last_digit = (size.columns / Digit::COLUMNS).saturating_sub(1).max(1) as f64;
+
+        // Where each clocklet comes in the order, from 0 (first) to 1 (last)
+        let order = |col: usize, row: usize| -> f64 {
+            match self {
+                Self::Sweep(_) => col as f64 / last_col,
+                Self::Radial(_) => {
+                    (col as f64 - mid_col).hypot(row as f64 - mid_row) / max_distance
+                }
+                Self::Random(_) => random(),
+                Self::Digits(_) => ((col / Digit::COLUMNS) as f64 / last_digit).min(1.0),
+            }
+        };
+
+        let spread = self.spread();
+        Timings(size.grid(|col, row| Timing {
+            delay: order(col, row) * spread,
+            duration: 1.0 - spread,
+        }))
+    }
+}
+
+impl std::str::FromStr for Stagger {
+    type Err = String;
+
+    /// Parse `<kind>` or `<kind>:<spread>`, e.g. `radial:0.4`.
+    /// Kinds are `sweep`, `radial`, `random` and `digits`; spread defaults to 0.5.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, spread) = match s.trim().split_once(':') {
+            Some((kind, spread)) => {
+                let spread = spread
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|e| format!("bad stagger spread {spread:?}: {e}"))?;
+                if !(0.0..=1.0).contains(&spread) {
+                    return Err(format!("stagger spread must be within 0..=1, got {spread}"));
+                }
+                (kind.trim(), spread)
+            }
+            None => (s.trim(), 0.5),
+        };
+        match kind {
+            "sweep" => Ok(Self::Sweep(spread)),
+            "radial" => Ok(Self::Radial(spread)),
+            "random" => Ok(Self::Random(spread)),
+            "digits" => Ok(Self::Digits(spread)),
+            _ => Err(format!(
+                "unknown stagger {kind:?}, expected one of sweep, radial, random, digits"
+            )),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Counting up instead of telling the time, with laps.
+
+use tracing::debug;
+
+use std::time::Duration;
+
+use super::{
+    ClockTarget, Digit, Size,
+    readout::{self, Readout},
+};
+
+/// A stopwatch, shown as MM:SS for the first hour and HH:MM after that. It times the
+/// animation's own elapsed time while it's running, so stopping and starting it, or the
+/// window being stalled, lose nothing.
+///
+/// Taking a lap holds what's showing for [`Self::LAP_HOLD_SECONDS`] while it keeps on timing.
+///
+/// ```
+/// use klox::clock::{Size, stopwatch::Stopwatch};
+/// use std::time::Duration;
+///
+/// let secs = Duration::from_secs_f64;
+/// let size = Size::default();
+/// let mut stopwatch = Stopwatch::default();
+///
+/// // 00:00 until it's started
+/// assert!(stopwatch.trigger(secs(0.0), size).is_some());
+/// assert!(stopwatch.trigger(secs(5.0), size).is_none());
+///
+/// stopwatch.toggle();
+/// assert!(stopwatch.trigger(secs(10.0), size).is_none());
+/// assert!(stopwatch.trigger(secs(11.0), size).is_some());
+///
+/// // Held at 00:01 for a while, then straight on to where it's got to
+/// stopwatch.lap();
+/// assert!(stopwatch.trigger(secs(12.0), size).is_none());
+/// assert!(stopwatch.trigger(secs(14.9), size).is_none());
+/// assert!(stopwatch.trigger(secs(15.0), size).is_some());
+/// assert_eq!(stopwatch.elapsed(), secs(5.0));
+///
+/// stopwatch.toggle();
+/// assert!(stopwatch.trigger(secs(20.0), size).is_none());
+/// assert_eq!(stopwatch.elapsed(), secs(5.0));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Stopwatch {
+    elapsed: Duration,
+    running: bool,
+    /// Animation time as of the last trigger while running
+    last: Option<Duration>,
+    /// A lap being held, and until when, once it's been shown
+    lap: Option<([Digit; 4], Option<Duration>)>,
+    readout: Readout,
+}
+
+impl Stopwatch {
+    /// How long a lap's held for
+    pub const LAP_HOLD_SECONDS: u64 = 3;
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Start if it's stopped, stop if it's running
+    pub fn toggle(&mut self) {
+        self.running = !self.running;
+        self.last = None;
+    }
+
+    /// Back to 00:00, stopped
+    pub fn reset(&mut self) {
+        *self = Self {
+            readout: self.readout.clone(),
+            ..Self::default()
+        };
+    }
+
+    /// Hold what's showing for a while, carrying on timing underneath
+    pub fn lap(&mut self) {
+        debug!("⏱️ lap at {:?}", self.elapsed);
+        self.lap = Some((self.digits(), None));
+    }
+
+    /// Put the time so far, or the lap, back up on the next trigger
+    pub fn redraw(&mut self) {
+        self.readout.redraw();
+    }
+
+    /// Time up to `elapsed`, the time since the animation started, and return a target for a
+    /// clock of the given `size` if what's showing needs to change
+    pub fn trigger(&mut self, elapsed: Duration, size: Size) -> Option<ClockTarget> {
+        if self.running {
+            let last = self.last.replace(elapsed).unwrap_or(elapsed);
+            self.elapsed += elapsed.saturating_sub(last);
+        }
+
+        let digits = match &mut self.lap {
+            Some((_, Some(until))) if *until <= elapsed => {
+                self.lap = None;
+                self.digits()
+            }
+            Some((digits, until)) => {
+                until.get_or_insert(elapsed + Duration::from_secs(Self::LAP_HOLD_SECONDS));
+                *digits
+            }
+            None => self.digits(),
+        };
+        self.readout.show(digits, size)
+    }
+
+    /// Whole seconds, or whole minutes after the first hour, only ever counting what's passed
+    fn digits(&self) -> [Digit; 4] {
+        readout::digits(self.elapsed.as_secs(), false)
+    }
+}
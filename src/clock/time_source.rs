@@ -0,0 +1,179 @@
+//! Where the clock gets its idea of "now" from.
+//!
+//! Everything time-dependent goes through [`TimeSource`] so it can be driven by something other
+//! than the system clock: [`ManualTime`] for tests, [`Accelerated`] for watching a whole day of
+//! minute changes go by.
+
+use time::OffsetDateTime;
+use tracing::warn;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::timezone::Zone;
+
+pub trait TimeSource: Send + Sync {
+    /// The current wall-clock time. The offset is irrelevant, it's the instant that counts;
+    /// conversion for display is a [`Zone`]'s job.
+    fn now(&self) -> OffsetDateTime;
+
+    /// Time elapsed on a clock which never jumps, measured from an arbitrary fixed point.
+    /// Comparing this against [`Self::now`] is how wall-clock jumps are spotted.
+    fn monotonic(&self) -> Duration;
+}
+
+/// The real thing
+pub struct SystemTime {
+    start: Instant,
+}
+
+impl Default for SystemTime {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl TimeSource for SystemTime {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same underlying time, so keep one around to step a clone you've handed off.
+///
+/// ```
+/// use klox::clock::time_source::{ManualTime, TimeSource};
+/// use std::time::Duration;
+/// use time::macros::datetime;
+///
+/// let time = ManualTime::new(datetime!(2025-06-01 12:00:30 UTC));
+/// let handle = time.clone();
+///
+/// handle.step(Duration::from_secs(10));
+/// assert_eq!(time.now(), datetime!(2025-06-01 12:00:40 UTC));
+/// assert_eq!(time.monotonic(), Duration::from_secs(10));
+///
+/// // Jumps only move the wall clock
+/// handle.jump(time::Duration::hours(-1));
+/// assert_eq!(time.now(), datetime!(2025-06-01 11:00:40 UTC));
+/// assert_eq!(time.monotonic(), Duration::from_secs(10));
+/// ```
+#[derive(Clone)]
+pub struct ManualTime(Arc<Mutex<ManualState>>);
+
+struct ManualState {
+    now: OffsetDateTime,
+    monotonic: Duration,
+}
+
+impl ManualTime {
+    pub fn new(now: OffsetDateTime) -> Self {
+        Self(Arc::new(Mutex::new(ManualState {
+            now,
+            monotonic: Duration::ZERO,
+        })))
+    }
+
+    /// Let time pass normally
+    pub fn step(&self, duration: Duration) {
+        let mut state = self.0.lock().unwrap();
+        state.now += duration;
+        state.monotonic += duration;
+    }
+
+    /// Move the wall clock without any time passing, like a suspend/resume or NTP step
+    pub fn jump(&self, by: time::Duration) {
+        self.0.lock().unwrap().now += by;
+    }
+
+    /// Set the wall clock outright, also without any time passing
+    pub fn set(&self, now: OffsetDateTime) {
+        self.0.lock().unwrap().now = now;
+    }
+}
+
+impl TimeSource for ManualTime {
+    fn now(&self) -> OffsetDateTime {
+        self.0.lock().unwrap().now
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.0.lock().unwrap().monotonic
+    }
+}
+
+/// Runs from a given start time at some multiple of real time.
+///
+/// With a `period` set, time wraps back to `start` once that much (accelerated) time has
+/// passed, which the trigger sees as a jump and resyncs from.
+pub struct Accelerated {
+    start: OffsetDateTime,
+    began: Instant,
+    rate: f64,
+    period: Option<Duration>,
+}
+
+impl Accelerated {
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    pub fn new(start: OffsetDateTime, rate: f64) -> Self {
+        Self {
+            start,
+            began: Instant::now(),
+            rate,
+            period: None,
+        }
+    }
+
+    /// Replay the day containing `at` from midnight, over and over, `rate` times faster than
+    /// real time. A `rate` of 60 gets through the day in 24 minutes.
+    ///
+    /// Midnight is taken in `at`'s offset, so pass a local time to replay a local day.
+    pub fn replay_day(at: OffsetDateTime, rate: f64) -> Self {
+        Self {
+            period: Some(Self::DAY),
+            ..Self::new(at.replace_time(time::Time::MIDNIGHT), rate)
+        }
+    }
+}
+
+impl TimeSource for Accelerated {
+    fn now(&self) -> OffsetDateTime {
+        let mut elapsed = self.monotonic();
+        if let Some(period) = self.period {
+            elapsed = Duration::from_secs_f64(elapsed.as_secs_f64() % period.as_secs_f64());
+        }
+        self.start + elapsed
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.began.elapsed().mul_f64(self.rate)
+    }
+}
+
+/// Pick a time source according to the `KLOX_TIME` environment variable:
+/// - unset or `system`: the system clock
+/// - `replay`: today (in `zone`) replayed at 60x
+pub fn from_env(zone: &Zone) -> Box<dyn TimeSource> {
+    match std::env::var("KLOX_TIME").as_deref() {
+        Err(_) | Ok("system") => Box::new(SystemTime::default()),
+        Ok("replay") => {
+            let today = zone.to_local(OffsetDateTime::now_utc());
+            Box::new(Accelerated::replay_day(today, 60.0))
+        }
+        Ok(other) => {
+            warn!("unknown KLOX_TIME {other:?}, using the system clock");
+            Box::new(SystemTime::default())
+        }
+    }
+}
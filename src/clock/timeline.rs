@@ -0,0 +1,291 @@
+//! Choreography: several poses in a row, as one reusable value.
+//!
+//! A [`Timeline`] is a list of [`Keyframe`]s which a [`super::Clock`] turns into
+//! [`ClockTarget`]s one at a time, as it's ready for them.
+
+use std::time::Duration;
+
+use super::{
+    ClockTarget, Clocklet, Lifespan, Size,
+    direction::Direction,
+    easing::{Curve, Easing},
+    stagger::Stagger,
+    transition::Transition,
+};
+
+/// Where a keyframe puts the hands
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pose {
+    /// Exactly these clocklets, as columns of rows, centred on the clock as
+    /// [`ClockTarget::centred`] does if they're not the same size
+    Clocklets(Vec<Vec<Clocklet>>),
+    Horizontals,
+    Verticals,
+    Slashies,
+    /// Every hand somewhere random, different each time round
+    Scatter,
+    /// Wherever the timeline is headed, see [`Timeline::towards`].
+    /// Stays put if it isn't headed anywhere.
+    Destination,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keyframe {
+    pub pose: Pose,
+    /// How long it takes to get into the pose
+    pub millis: u64,
+    /// How long to stay in the pose once there
+    pub hold_millis: u64,
+    pub transition: Transition,
+    /// Whole turns to make on the way, on top of getting into position
+    pub extra_turns: f64,
+}
+
+impl Keyframe {
+    pub fn new(pose: Pose, millis: u64) -> Self {
+        Self {
+            pose,
+            millis,
+            hold_millis: 0,
+            transition: Transition::default(),
+            extra_turns: 0.0,
+        }
+    }
+
+    pub fn hold(mut self, millis: u64) -> Self {
+        self.hold_millis = millis;
+        self
+    }
+
+    pub fn transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    pub fn extra_turns(mut self, turns: f64) -> Self {
+        self.extra_turns = turns;
+        self
+    }
+}
+
+/// What to do after the last keyframe
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Playback {
+    /// Stop
+    #[default]
+    Once,
+    /// Go back to the first keyframe
+    Loop,
+    /// Run back through the keyframes to the first, then forwards again, and so on
+    PingPong,
+}
+
+/// A sequence of timed keyframes.
+///
+/// ```
+/// use klox::clock::Clocklet;
+/// use klox::clock::timeline::{Keyframe, Playback, Pose, Timeline};
+///
+/// let mut timeline = Timeline::new(
+///     vec![
+///         Keyframe::new(Pose::Horizontals, 500),
+///         Keyframe::new(Pose::Verticals, 500).hold(1000),
+///         Keyframe::new(Pose::Clocklets(vec![vec![Clocklet::U; 3]; 8]), 500),
+///     ],
+///     Playback::PingPong,
+/// );
+/// assert_eq!(timeline.duration().as_millis(), 2500);
+///
+/// let from = vec![vec![Clocklet::D; 3]; 8];
+/// let poses: Vec<_> = std::iter::from_fn(|| timeline.next_target(&from))
+///     .map(|target| target.clocklets()[0][0])
+///     .take(8)
+///     .collect();
+///
+/// // Forwards, with the hold as a target of its own, then back again
+/// let (h, v, u) = (Clocklet::H, Clocklet::V, Clocklet::U);
+/// assert_eq!(poses, [h, v, v, u, v, v, h, v]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Timeline {
+    keyframes: Vec<Keyframe>,
+    playback: Playback,
+    /// Clocklets for [`Pose::Destination`]
+    destination: Option<Vec<Vec<Clocklet>>>,
+    /// Index of the next keyframe to play
+    cursor: usize,
+    /// Which way we're currently going through the keyframes, for [`Playback::PingPong`]
+    forwards: bool,
+    /// The pose to hold before moving on, if the last keyframe asked for one
+    hold: Option<ClockTarget>,
+}
+
+impl Timeline {
+    pub fn new(keyframes: Vec<Keyframe>, playback: Playback) -> Self {
+        Self {
+            keyframes,
+            playback,
+            destination: None,
+            cursor: 0,
+            forwards: true,
+            hold: None,
+        }
+    }
+
+    /// Scatter the hands, swirl them round, then settle on the destination
+    pub fn minute_change() -> Self {
+        let swirl = Transition {
+            easing: Easing::InOut(Curve::Sine),
+            direction: Direction::Opposing,
+            stagger: Some(Stagger::Radial(0.4)),
+        };
+        let settle = Transition {
+            easing: Easing::Out(Curve::Back),
+            direction: Direction::Shortest,
+            stagger: Some(Stagger::Digits(0.3)),
+        };
+        Self::new(
+            vec![
+                Keyframe::new(Pose::Scatter, 1000).transition(Transition {
+                    easing: Easing::Out(Curve::Cubic),
+                    ..Default::default()
+                }),
+                Keyframe::new(Pose::Slashies, 2000)
+                    .transition(swirl)
+                    .extra_turns(1.0),
+                Keyframe::new(Pose::Destination, 2000).transition(settle),
+            ],
+            Playback::Once,
+        )
+    }
+
+    /// Slashies, horizontals and a couple of scatters, round and round, for waking up to
+    pub fn wake_up() -> Self {
+        let whirl = Transition {
+            easing: Easing::InOut(Curve::Cubic),
+            direction: Direction::Opposing,
+            stagger: Some(Stagger::Sweep(0.3)),
+        };
+        let jolt = Transition {
+            easing: Easing::Out(Curve::Back),
+            ..Default::default()
+        };
+        Self::new(
+            vec![
+                Keyframe::new(Pose::Slashies, 800)
+                    .transition(whirl)
+                    .extra_turns(1.0)
+                    .hold(200),
+                Keyframe::new(Pose::Horizontals, 800)
+                    .transition(whirl)
+                    .hold(200),
+                Keyframe::new(Pose::Scatter, 400).transition(jolt),
+                Keyframe::new(Pose::Scatter, 400).transition(jolt),
+            ],
+            Playback::Loop,
+        )
+    }
+
+    /// Head for `target`'s pose, for the benefit of any [`Pose::Destination`] keyframes.
+    /// If `target`'s lifespan hasn't started yet, the timeline is also sped up or slowed
+    /// down so one pass through it takes just as long.
+    pub fn towards(mut self, target: ClockTarget) -> Self {
+        if let Lifespan::Pending(duration) = target.lifespan {
+            self = self.fit(duration);
+        }
+        self.destination = Some(target.clocklets);
+        self
+    }
+
+    /// The pose any [`Pose::Destination`] keyframes head for, if it's been given
+    pub fn destination(&self) -> Option<&[Vec<Clocklet>]> {
+        self.destination.as_deref()
+    }
+
+    /// Scale every keyframe's timings so one pass through takes `duration`
+    pub fn fit(mut self, duration: Duration) -> Self {
+        let current = self.duration().as_secs_f64();
+        if current == 0.0 {
+            return self;
+        }
+        let scale = duration.as_secs_f64() / current;
+        for keyframe in self.keyframes.iter_mut() {
+            keyframe.millis = (keyframe.millis as f64 * scale).round() as u64;
+            keyframe.hold_millis = (keyframe.hold_millis as f64 * scale).round() as u64;
+        }
+        self
+    }
+
+    /// How long one pass through the keyframes takes, holds included
+    pub fn duration(&self) -> Duration {
+        let millis = self
+            .keyframes
+            .iter()
+            .map(|keyframe| keyframe.millis + keyframe.hold_millis)
+            .sum();
+        Duration::from_millis(millis)
+    }
+
+    /// The next target to play on a clock whose hands are at `from`, or `None` once finished
+    pub fn next_target(&mut self, from: &[Vec<Clocklet>]) -> Option<ClockTarget> {
+        if let Some(hold) = self.hold.take() {
+            return Some(hold);
+        }
+
+        let size = Size::new(from.len(), from.first().map_or(0, Vec::len));
+        let keyframe = self.keyframes.get(self.cursor)?;
+        let lifespan = Lifespan::from_millis(keyframe.millis);
+        let target = match &keyframe.pose {
+            Pose::Clocklets(clocklets) => ClockTarget::centred(clocklets, size, lifespan),
+            Pose::Horizontals => ClockTarget::horizontals(size, lifespan),
+            Pose::Verticals => ClockTarget::verticals(size, lifespan),
+            Pose::Slashies => ClockTarget::slashies(size, lifespan),
+            Pose::Scatter => ClockTarget {
+                clocklets: size.grid(|_, _| Default::default()),
+                lifespan,
+                ..Default::default()
+            },
+            Pose::Destination => ClockTarget {
+                clocklets: self.destination.as_deref().unwrap_or(from).to_vec(),
+                lifespan,
+                ..Default::default()
+            },
+        };
+        let mut target = keyframe.transition.apply(target);
+        if keyframe.extra_turns != 0.0 {
+            target.extra_turns = Some(size.grid(|_, _| keyframe.extra_turns));
+        }
+
+        if keyframe.hold_millis > 0 {
+            self.hold = Some(ClockTarget {
+                clocklets: target.clocklets.clone(),
+                lifespan: Lifespan::from_millis(keyframe.hold_millis),
+                ..Default::default()
+            });
+        }
+        self.advance();
+
+        Some(target)
+    }
+
+    fn advance(&mut self) {
+        let last = self.keyframes.len().saturating_sub(1);
+        match self.playback {
+            Playback::Once => self.cursor += 1,
+            Playback::Loop => self.cursor = (self.cursor + 1) % self.keyframes.len(),
+            Playback::PingPong if last == 0 => {}
+            Playback::PingPong => {
+                if self.forwards && self.cursor == last {
+                    self.forwards = false;
+                } else if !self.forwards && self.cursor == 0 {
+                    self.forwards = true;
+                }
+                if self.forwards {
+                    self.cursor += 1;
+                } else {
+                    self.cursor -= 1;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Working out which offset to display an instant in.
+//!
+//! `UtcOffset::current_local_offset` refuses to work in multi-threaded processes on Linux (and
+//! nannou is very multi-threaded), so we read the zone ourselves from the system tzdata instead.
+
+use time::{OffsetDateTime, UtcOffset};
+use tracing::{debug, warn};
+
+/// A time zone, as understood by the local tzdata.
+///
+/// Offsets are looked up per instant, so conversions either side of a DST transition come out
+/// right.
+///
+/// ```
+/// use klox::clock::timezone::Zone;
+/// use time::macros::{datetime, offset};
+///
+/// // A POSIX rule for the UK, so this doesn't rely on tzdata being installed
+/// let zone = Zone::named("GMT0BST,M3.5.0/1,M10.5.0").unwrap();
+///
+/// // The clocks go forward at 01:00 UTC on the last Sunday of March
+/// let before = zone.to_local(datetime!(2025-03-30 00:59:59 UTC));
+/// let after = zone.to_local(datetime!(2025-03-30 01:00:00 UTC));
+/// assert_eq!(before.offset(), offset!(UTC));
+/// assert_eq!(after.offset(), offset!(+1));
+/// assert_eq!(after.hour(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Zone {
+    name: String,
+    tz: tz::TimeZone,
+}
+
+impl Zone {
+    pub fn utc() -> Self {
+        Self {
+            name: "UTC".to_string(),
+            tz: tz::TimeZone::utc(),
+        }
+    }
+
+    /// Look up an IANA zone name (e.g. `Europe/London`) in the system tzdata, or parse a
+    /// POSIX TZ string (e.g. `CET-1CEST,M3.5.0,M10.5.0/3`) if there's no such file.
+    pub fn named(name: &str) -> Result<Self, tz::Error> {
+        Ok(Self {
+            name: name.trim_start_matches(':').to_string(),
+            tz: tz::TimeZone::from_posix_tz(name)?,
+        })
+    }
+
+    /// Work out which zone to display in, trying in order:
+    /// - the explicitly `configured` zone name, if any
+    /// - the `TZ` environment variable
+    /// - the system's `/etc/localtime`
+    /// - UTC, with a warning
+    pub fn resolve(configured: Option<&str>) -> Self {
+        if let Some(name) = configured {
+            match Self::named(name) {
+                Ok(zone) => return zone,
+                Err(e) => warn!("couldn't load configured time zone {name:?}: {e}"),
+            }
+        }
+        match std::env::var("TZ") {
+            Ok(name) if !name.is_empty() => match Self::named(&name) {
+                Ok(zone) => return zone,
+                Err(e) => warn!("couldn't load time zone from TZ={name:?}: {e}"),
+            },
+            _ => {}
+        }
+        match tz::TimeZone::local() {
+            Ok(tz) => {
+                return Self {
+                    name: "localtime".to_string(),
+                    tz,
+                };
+            }
+            Err(e) => warn!("couldn't load the system time zone: {e}"),
+        }
+        warn!("falling back to UTC");
+        Self::utc()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The offset from UTC in effect at a given instant
+    pub fn offset_at(&self, at: OffsetDateTime) -> UtcOffset {
+        self.tz
+            .find_local_time_type(at.unix_timestamp())
+            .map_err(|e| e.to_string())
+            .and_then(|t| UtcOffset::from_whole_seconds(t.ut_offset()).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                debug!("no offset for {at} in {}: {e}", self.name);
+                UtcOffset::UTC
+            })
+    }
+
+    /// The same instant, expressed in this zone's local time
+    pub fn to_local(&self, at: OffsetDateTime) -> OffsetDateTime {
+        at.to_offset(self.offset_at(at))
+    }
+}
+
+impl Default for Zone {
+    fn default() -> Self {
+        Self::utc()
+    }
+}
@@ -0,0 +1,37 @@
+//! How each kind of [`ClockTarget`] moves: its easing curve, which way the hands turn and any
+//! stagger across clocklets.
+
+use super::{ClockTarget, direction::Direction, easing::Easing, stagger::Stagger};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Transition {
+    pub easing: Easing,
+    pub direction: Direction,
+    pub stagger: Option<Stagger>,
+}
+
+impl Transition {
+    pub fn apply(&self, target: ClockTarget) -> ClockTarget {
+        let target = target
+            .with_easing(self.easing)
+            .with_direction(self.direction);
+        match self.stagger {
+            Some(stagger) => target.with_stagger(stagger),
+            None => target,
+        }
+    }
+}
+
+/// Which transition to use for each kind of target. By default they're all linear, turning
+/// the shortest way, with every clocklet moving in lockstep.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Transitions {
+    /// Moving to show a new time
+    pub time: Transition,
+    /// The [`ClockTarget::random_millis`] scramble
+    pub scramble: Transition,
+    /// Digits pushed by hand from the keyboard
+    pub digit: Transition,
+    /// Whole-clock patterns, like [`ClockTarget::slashies`]
+    pub pattern: Transition,
+}
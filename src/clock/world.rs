@@ -0,0 +1,176 @@
+//! Telling the time in several places in turn, for teams spread around the world.
+
+use time::OffsetDateTime;
+use tracing::debug;
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use super::timezone::Zone;
+use crate::render::Color;
+
+/// A place to tell the time in, optionally with its own tint for the faces.
+///
+/// ```
+/// use klox::clock::world::WorldZone;
+/// use klox::render::Color;
+///
+/// let place: WorldZone = "JST-9 #ffe0e0".parse().unwrap();
+/// assert_eq!(place.zone.name(), "JST-9");
+/// assert_eq!(place.tint, Some(Color([0xff, 0xe0, 0xe0, 0xff])));
+/// assert_eq!(place.to_string(), "JST-9 #ffe0e0");
+///
+/// assert_eq!("UTC".parse::<WorldZone>().unwrap().tint, None);
+/// assert!("UTC red".parse::<WorldZone>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorldZone {
+    pub zone: Zone,
+    /// Drawn on the faces instead of their usual color while this zone's showing, or one from
+    /// [`TriggerZone::PALETTE`] if not
+    pub tint: Option<Color>,
+}
+
+impl FromStr for WorldZone {
+    type Err = String;
+
+    /// Parse an IANA zone name (or a POSIX TZ string), optionally followed by a color as for
+    /// [`Color`]'s [`FromStr`], e.g. `America/New_York #e0e8ff`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, tint) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+        let zone = Zone::named(name).map_err(|e| format!("{name:?} isn't a time zone: {e}"))?;
+        let tint = match tint.trim() {
+            "" => None,
+            tint => Some(tint.parse()?),
+        };
+        Ok(Self { zone, tint })
+    }
+}
+
+impl fmt::Display for WorldZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.zone.name())?;
+        if let Some(tint) = self.tint {
+            write!(f, " {tint}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Decides which zone the time's told in: the home zone, or each of the world zones in turn for
+/// [`Self::every`], lined up with the Unix epoch so every clock cycling through the same list
+/// agrees on which one's showing.
+///
+/// Each world zone always has a tint, so there's no mistaking one for another: its own, or the
+/// next from [`Self::PALETTE`] by its place in the list.
+///
+/// ```
+/// use klox::clock::{timezone::Zone, world::TriggerZone};
+/// use klox::render::Color;
+/// use std::time::Duration;
+/// use time::macros::datetime;
+///
+/// let mut trigger = TriggerZone::new(Zone::utc());
+///
+/// // Just the home zone until there are others
+/// let zone = trigger.trigger(datetime!(2025-06-01 12:00:00 UTC));
+/// assert_eq!(zone.map(Zone::name), Some("UTC"));
+/// assert!(trigger.tint().is_none());
+/// assert!(trigger.trigger(datetime!(2025-06-01 12:00:10 UTC)).is_none());
+///
+/// let places = vec!["EST5".parse().unwrap(), "JST-9 #ffe0e0".parse().unwrap()];
+/// trigger.set_zones(places, Duration::from_secs(10));
+/// let zone = trigger.trigger(datetime!(2025-06-01 12:00:10 UTC));
+/// assert_eq!(zone.map(Zone::name), Some("JST-9"));
+/// assert_eq!(trigger.tint(), Some(Color([0xff, 0xe0, 0xe0, 0xff])));
+/// assert!(trigger.trigger(datetime!(2025-06-01 12:00:19 UTC)).is_none());
+/// let zone = trigger.trigger(datetime!(2025-06-01 12:00:20 UTC));
+/// assert_eq!(zone.map(Zone::name), Some("EST5"));
+/// assert_eq!(trigger.tint(), Some(TriggerZone::PALETTE[0]));
+/// ```
+pub struct TriggerZone {
+    /// Where the time's told when there aren't any world zones, and where alarms go off
+    home: Zone,
+    zones: Vec<WorldZone>,
+    /// How long each world zone's shown for
+    every: Duration,
+    /// Index of the world zone showing, or 0 for home if there aren't any, once it's been
+    /// handed out
+    shown: Option<usize>,
+}
+
+impl TriggerZone {
+    /// The default time each world zone's shown for
+    pub const EVERY_SECONDS: u64 = 10;
+    /// Tints for world zones that don't have their own, light enough for black hands
+    pub const PALETTE: [Color; 6] = [
+        Color([0xd8, 0xe8, 0xff, 0xff]),
+        Color([0xff, 0xe0, 0xc8, 0xff]),
+        Color([0xd8, 0xf4, 0xd8, 0xff]),
+        Color([0xf0, 0xd8, 0xff, 0xff]),
+        Color([0xff, 0xf4, 0xc0, 0xff]),
+        Color([0xc8, 0xf0, 0xf0, 0xff]),
+    ];
+
+    /// Telling the time in `home`, with no world zones
+    pub fn new(home: Zone) -> Self {
+        Self {
+            home,
+            zones: Vec::new(),
+            every: Duration::from_secs(Self::EVERY_SECONDS),
+            shown: None,
+        }
+    }
+
+    pub fn home(&self) -> &Zone {
+        &self.home
+    }
+
+    /// Make `home` the home zone, switching to it on the next trigger if it's the one showing
+    pub fn set_home(&mut self, home: Zone) {
+        if home != self.home {
+            self.home = home;
+            self.shown = None;
+        }
+    }
+
+    /// Cycle through `zones` instead of telling the time at home, each for `every`, or go back
+    /// home if there aren't any
+    pub fn set_zones(&mut self, zones: Vec<WorldZone>, every: Duration) {
+        if zones != self.zones {
+            self.zones = zones;
+            self.shown = None;
+        }
+        self.every = every;
+    }
+
+    /// How long each world zone's shown for
+    pub fn every(&self) -> Duration {
+        self.every
+    }
+
+    /// The tint for the world zone showing, or `None` at home
+    pub fn tint(&self) -> Option<Color> {
+        let i = self.shown?;
+        let place = self.zones.get(i)?;
+        Some(place.tint.unwrap_or(Self::PALETTE[i % Self::PALETTE.len()]))
+    }
+
+    /// The zone to tell the time in from `now`, if that's changed
+    pub fn trigger(&mut self, now: OffsetDateTime) -> Option<&Zone> {
+        let due = match self.zones.len() {
+            0 => 0,
+            len => {
+                let every = self.every.as_secs().max(1);
+                (now.unix_timestamp().div_euclid(every as i64) as u64 % len as u64) as usize
+            }
+        };
+        if self.shown == Some(due) {
+            return None;
+        }
+        self.shown = Some(due);
+
+        let zone = self.zones.get(due).map_or(&self.home, |place| &place.zone);
+        debug!("🌍 telling the time in {}", zone.name());
+        Some(zone)
+    }
+}
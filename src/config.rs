@@ -0,0 +1,510 @@
+//! Settings for how the clock looks and moves, from a TOML file.
+//!
+//! Everything's optional: anything left out keeps its built-in value, so an empty file is the
+//! same as no file. See `config.example.toml` for every setting. The environment still has the
+//! last word: `KLOX_GRID` and `KLOX_TZ` override the file's `layout.grid` and `time.zone`.
+//!
+//! ```
+//! use klox::clock::transition::Transitions;
+//! use klox::config::Config;
+//! use klox::render::Color;
+//!
+//! let config = Config::from_toml(
+//!     r##"
+//!     [colors]
+//!     face = "#202020"
+//!     hand = "white"
+//!
+//!     [transitions.time]
+//!     easing = "out-bounce"
+//!     "##,
+//! )
+//! .unwrap();
+//! assert_eq!(config.colors.hand, Color::WHITE);
+//! assert_eq!(config.layout, Default::default());
+//!
+//! // Mistakes say where they are
+//! let e = Config::from_toml("[layout]\nhand_weight = 0").unwrap_err();
+//! assert_eq!(e, "layout.hand_weight must be more than 0, got 0");
+//! let e = Config::from_toml("[colors]\nface = \"red\"").unwrap_err();
+//! assert!(e.contains("line 2") && e.contains("bad color \"red\""));
+//! let e = Config::from_toml("[layuot]").unwrap_err();
+//! assert!(e.contains("unknown field `layuot`"));
+//!
+//! // The example is the defaults, written out
+//! let example = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/config.example.toml"));
+//! let example = Config::from_toml(example).unwrap();
+//! assert_eq!(example.transitions.transitions(), Transitions::default());
+//! let rest = Config { transitions: Default::default(), ..example };
+//! assert_eq!(rest, Config::default());
+//! ```
+
+use serde::{Deserialize, Deserializer, de};
+use tracing::{debug, warn};
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::assets::Background;
+use crate::clock::{
+    Clocklet, Hours, Size, TimeFormat, TriggerTime,
+    alarm::{Alarm, Alarms},
+    date::{DateOrder, TriggerDate},
+    direction::Direction,
+    easing::Easing,
+    stagger::Stagger,
+    timezone::Zone,
+    transition::{Transition, Transitions},
+    world::{TriggerZone, WorldZone},
+};
+use crate::render::Color;
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub layout: Layout,
+    pub colors: Colors,
+    pub timings: Timings,
+    pub transitions: TransitionStyles,
+    pub background: BackgroundSettings,
+    pub time: TimeSettings,
+    pub date: DateSettings,
+    pub alarms: AlarmSettings,
+    pub world: WorldSettings,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Layout {
+    /// Columns and rows of clocklets, e.g. `"24x9"`, if not just enough for the time
+    #[serde(deserialize_with = "parse_some")]
+    pub grid: Option<Size>,
+    /// Around the whole clock, in pixels
+    pub padding: f32,
+    /// Around each clocklet, in pixels
+    pub clocklet_padding: f32,
+    /// As a fraction of the face diameter
+    pub hand_length: f32,
+    /// In pixels
+    pub hand_weight: f32,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            grid: None,
+            padding: 10.0,
+            clocklet_padding: 8.0,
+            hand_length: Clocklet::HAND_LENGTH,
+            hand_weight: Clocklet::HAND_WEIGHT,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Colors {
+    #[serde(deserialize_with = "parse")]
+    pub face: Color,
+    #[serde(deserialize_with = "parse")]
+    pub hand: Color,
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Self {
+            face: Color::WHITE,
+            hand: Color::BLACK,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Timings {
+    /// How far ahead of each minute the hands set off for it
+    pub lead_millis: u64,
+    /// How long getting to the right time takes on startup or after the clock jumps, at most
+    /// the lead time
+    pub sync_millis: u64,
+    /// How long the scramble takes
+    pub scramble_millis: u64,
+    /// How long the horizontals, verticals and slashies patterns take
+    pub pattern_millis: u64,
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self {
+            lead_millis: TriggerTime::LEAD_TIME_SECONDS as u64 * 1000,
+            sync_millis: TriggerTime::SYNC_MILLIS,
+            scramble_millis: 3000,
+            pattern_millis: 5000,
+        }
+    }
+}
+
+/// Changes to each kind of [`Transition`], see [`Transitions`]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransitionStyles {
+    /// Play the minute change choreography on the way to each new minute
+    pub choreography: bool,
+    pub time: TransitionStyle,
+    pub scramble: TransitionStyle,
+    pub digit: TransitionStyle,
+    pub pattern: TransitionStyle,
+}
+
+impl TransitionStyles {
+    /// The built-in transitions, with these changes
+    pub fn transitions(&self) -> Transitions {
+        let builtin = Transitions::default();
+        Transitions {
+            time: self.time.over(builtin.time),
+            scramble: self.scramble.over(builtin.scramble),
+            digit: self.digit.over(builtin.digit),
+            pattern: self.pattern.over(builtin.pattern),
+        }
+    }
+}
+
+/// Whatever's given replaces that part of a [`Transition`]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransitionStyle {
+    /// As [`Easing`] parses it, e.g. `"in-out-cubic"`
+    #[serde(deserialize_with = "parse_some")]
+    pub easing: Option<Easing>,
+    /// As [`Direction`] parses it, e.g. `"clockwise"`
+    #[serde(deserialize_with = "parse_some")]
+    pub direction: Option<Direction>,
+    /// As [`Stagger`] parses it, e.g. `"radial:0.4"`, or `"none"`
+    #[serde(deserialize_with = "stagger")]
+    pub stagger: Option<Option<Stagger>>,
+}
+
+impl TransitionStyle {
+    pub fn over(&self, transition: Transition) -> Transition {
+        Transition {
+            easing: self.easing.unwrap_or(transition.easing),
+            direction: self.direction.unwrap_or(transition.direction),
+            stagger: self.stagger.unwrap_or(transition.stagger),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackgroundSettings {
+    /// `"builtin"`, `"plain"` or the path to an image
+    #[serde(deserialize_with = "parse")]
+    pub image: Background,
+    /// Behind the image, or instead of it
+    #[serde(deserialize_with = "parse")]
+    pub color: Color,
+}
+
+impl Default for BackgroundSettings {
+    fn default() -> Self {
+        Self {
+            image: Background::Builtin,
+            color: Color::BLACK,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeSettings {
+    /// IANA zone name, e.g. `"Europe/London"`, as for [`Zone::resolve`]
+    pub zone: Option<String>,
+    /// Show HH:MM:SS instead of HH:MM
+    pub seconds: bool,
+    /// `"24h"` or `"12h"`
+    #[serde(deserialize_with = "parse")]
+    pub hours: Hours,
+    /// `09:05` rather than ` 9:05`
+    pub leading_zero: bool,
+}
+
+impl Default for TimeSettings {
+    fn default() -> Self {
+        Self {
+            zone: None,
+            seconds: false,
+            hours: Hours::TwentyFour,
+            leading_zero: true,
+        }
+    }
+}
+
+impl TimeSettings {
+    pub fn format(&self) -> TimeFormat {
+        TimeFormat {
+            hours: self.hours,
+            leading_zero: self.leading_zero,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DateSettings {
+    /// `"dd.mm"` or `"mm/dd"`
+    #[serde(deserialize_with = "parse")]
+    pub order: DateOrder,
+    /// How often the date goes up by itself, or 0 for only when asked
+    pub every_seconds: u64,
+    /// How long it stays up
+    pub hold_seconds: u64,
+}
+
+impl Default for DateSettings {
+    fn default() -> Self {
+        Self {
+            order: DateOrder::DayMonth,
+            every_seconds: 0,
+            hold_seconds: TriggerDate::HOLD_SECONDS,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlarmSettings {
+    /// When to go off, e.g. `"07:30 weekdays"`, as for [`Alarm`]'s [`FromStr`]
+    #[serde(deserialize_with = "parse_each")]
+    pub at: Vec<Alarm>,
+    pub snooze_minutes: u64,
+    /// Flashed on the faces while an alarm's going off
+    #[serde(deserialize_with = "parse")]
+    pub tint: Color,
+}
+
+impl Default for AlarmSettings {
+    fn default() -> Self {
+        Self {
+            at: Vec::new(),
+            snooze_minutes: Alarms::SNOOZE_MINUTES,
+            tint: Color([0xff, 0x40, 0x40, 0xff]),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorldSettings {
+    /// Zones to tell the time in by turns instead of `time.zone`, e.g.
+    /// `"America/New_York #e0e8ff"`, as for [`WorldZone`]'s [`FromStr`]
+    #[serde(deserialize_with = "parse_each")]
+    pub zones: Vec<WorldZone>,
+    /// How long each one's shown for
+    pub every_seconds: u64,
+}
+
+impl Default for WorldSettings {
+    fn default() -> Self {
+        Self {
+            zones: Vec::new(),
+            every_seconds: TriggerZone::EVERY_SECONDS,
+        }
+    }
+}
+
+impl Config {
+    /// Parse and check a config file's contents
+    pub fn from_toml(toml: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(toml).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Read, parse and check the config file at `path`, then apply the environment's overrides
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let toml = std::fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+        let config =
+            Self::from_toml(&toml).map_err(|e| format!("error in {}: {e}", path.display()))?;
+        debug!("loaded config from {}", path.display());
+        Ok(config.with_env())
+    }
+
+    /// The config file at [`path`] if there is one, otherwise the defaults. If `KLOX_CONFIG`
+    /// names a file, it has to be there.
+    pub fn load_default() -> Result<Self, String> {
+        match path() {
+            Some(path) if path.exists() || std::env::var_os("KLOX_CONFIG").is_some() => {
+                Self::load(&path)
+            }
+            _ => Ok(Self::default().with_env()),
+        }
+    }
+
+    /// With `KLOX_GRID` and `KLOX_TZ`, if they're set, in place of `layout.grid` and `time.zone`
+    pub fn with_env(mut self) -> Self {
+        // `KLOX_GRID` takes <columns>x<rows>, e.g. "24x9"
+        if let Ok(grid) = std::env::var("KLOX_GRID") {
+            match grid.parse() {
+                Ok(size) => self.layout.grid = Some(size),
+                Err(e) => warn!("ignoring KLOX_GRID: {e}"),
+            }
+        }
+        // `KLOX_TZ` takes an IANA zone name, e.g. "Europe/London"
+        if let Ok(zone) = std::env::var("KLOX_TZ") {
+            self.time.zone = Some(zone);
+        }
+        self
+    }
+
+    /// Check everything's in range, naming the first setting that isn't
+    pub fn validate(&self) -> Result<(), String> {
+        fn check(ok: bool, key: &str, expected: &str, value: impl Display) -> Result<(), String> {
+            match ok {
+                true => Ok(()),
+                false => Err(format!("{key} must be {expected}, got {value}")),
+            }
+        }
+
+        let layout = &self.layout;
+        for (key, value) in [
+            ("layout.padding", layout.padding),
+            ("layout.clocklet_padding", layout.clocklet_padding),
+        ] {
+            check(value >= 0.0 && value.is_finite(), key, "0 or more", value)?;
+        }
+        check(
+            layout.hand_length > 0.0 && layout.hand_length <= 0.5,
+            "layout.hand_length",
+            "more than 0 and at most 0.5",
+            layout.hand_length,
+        )?;
+        check(
+            layout.hand_weight > 0.0 && layout.hand_weight.is_finite(),
+            "layout.hand_weight",
+            "more than 0",
+            layout.hand_weight,
+        )?;
+
+        let timings = &self.timings;
+        for (key, value) in [
+            ("timings.sync_millis", timings.sync_millis),
+            ("timings.scramble_millis", timings.scramble_millis),
+            ("timings.pattern_millis", timings.pattern_millis),
+        ] {
+            check(value > 0, key, "more than 0", value)?;
+        }
+        check(
+            timings.lead_millis > 0 && timings.lead_millis < 60_000,
+            "timings.lead_millis",
+            "more than 0 and less than a minute",
+            timings.lead_millis,
+        )?;
+
+        let date = &self.date;
+        check(
+            date.hold_seconds > 0,
+            "date.hold_seconds",
+            "more than 0",
+            date.hold_seconds,
+        )?;
+        check(
+            date.every_seconds == 0 || date.every_seconds > date.hold_seconds,
+            "date.every_seconds",
+            "0 or more than date.hold_seconds",
+            date.every_seconds,
+        )?;
+
+        check(
+            self.alarms.snooze_minutes > 0,
+            "alarms.snooze_minutes",
+            "more than 0",
+            self.alarms.snooze_minutes,
+        )?;
+        check(
+            self.world.every_seconds > 0,
+            "world.every_seconds",
+            "more than 0",
+            self.world.every_seconds,
+        )?;
+
+        if let Some(zone) = &self.time.zone {
+            Zone::named(zone).map_err(|e| format!("time.zone {zone:?} isn't a time zone: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// The zone to tell the time in, see [`Zone::resolve`]
+    pub fn zone(&self) -> Zone {
+        let zone = Zone::resolve(self.time.zone.as_deref());
+        debug!("displaying time in {}", zone.name());
+        zone
+    }
+}
+
+/// Where the config file is: `KLOX_CONFIG` if that's set, otherwise [`default_path`]
+pub fn path() -> Option<PathBuf> {
+    std::env::var_os("KLOX_CONFIG")
+        .map(PathBuf::from)
+        .or_else(default_path)
+}
+
+/// `$XDG_CONFIG_HOME/klox/config.toml`, or `~/.config/klox/config.toml`
+pub fn default_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_home.join("klox").join("config.toml"))
+}
+
+/// A string, parsed with [`FromStr`]
+fn parse<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(de::Error::custom)
+}
+
+/// A list of strings, each parsed with [`FromStr`]
+fn parse_each<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|s| s.parse().map_err(de::Error::custom))
+        .collect()
+}
+
+fn parse_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    parse(deserializer).map(Some)
+}
+
+/// A [`Stagger`], or `"none"` for none
+fn stagger<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Option<Stagger>>, D::Error> {
+    let stagger = String::deserialize(deserializer)?;
+    match stagger.trim() {
+        "none" => Ok(Some(None)),
+        stagger => stagger
+            .parse()
+            .map(|stagger| Some(Some(stagger)))
+            .map_err(de::Error::custom),
+    }
+}
@@ -0,0 +1,109 @@
+//! Just enough 2D geometry to lay clocks out, in the same coordinates whatever draws them:
+//! origin in the middle, x to the right and y up.
+//!
+//! The vectors are glam's, the same ones nannou uses, so they pass straight through to it.
+
+pub use glam::{Vec2, vec2};
+
+pub type Point2 = Vec2;
+
+pub fn pt2(x: f32, y: f32) -> Point2 {
+    vec2(x, y)
+}
+
+/// An axis-aligned rectangle
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rect {
+    /// Bottom left
+    min: Point2,
+    /// Top right
+    max: Point2,
+}
+
+impl Rect {
+    /// `w` wide and `h` high, centred on the origin
+    pub fn from_w_h(w: f32, h: f32) -> Self {
+        Self::from_xy_wh(Vec2::ZERO, vec2(w, h))
+    }
+
+    /// `wh` in size, centred on `xy`
+    pub fn from_xy_wh(xy: Point2, wh: Vec2) -> Self {
+        let half = wh.abs() / 2.0;
+        Self {
+            min: xy - half,
+            max: xy + half,
+        }
+    }
+
+    /// Between two opposite corners, in any order
+    pub fn from_corners(a: Point2, b: Point2) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    pub fn from_corner_points(a: [f32; 2], b: [f32; 2]) -> Self {
+        Self::from_corners(a.into(), b.into())
+    }
+
+    /// The centre
+    pub fn xy(&self) -> Point2 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn w(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn h(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn wh(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    pub fn w_h(&self) -> (f32, f32) {
+        (self.w(), self.h())
+    }
+
+    pub fn left(&self) -> f32 {
+        self.min.x
+    }
+
+    pub fn right(&self) -> f32 {
+        self.max.x
+    }
+
+    pub fn top(&self) -> f32 {
+        self.max.y
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.min.y
+    }
+
+    /// Left, top, width and height
+    pub fn l_t_w_h(&self) -> (f32, f32, f32, f32) {
+        (self.left(), self.top(), self.w(), self.h())
+    }
+
+    /// Shrunk by `pad` on every side, down to nothing at the centre if it's too small
+    pub fn pad(&self, pad: f32) -> Self {
+        let wh = (self.wh() - Vec2::splat(pad * 2.0)).max(Vec2::ZERO);
+        Self::from_xy_wh(self.xy(), wh)
+    }
+
+    /// Moved by `offset`
+    pub fn shift(&self, offset: Vec2) -> Self {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    pub fn shift_x(&self, x: f32) -> Self {
+        self.shift(vec2(x, 0.0))
+    }
+}
@@ -0,0 +1,348 @@
+//! Encoding rendered frames as animations: GIFs natively, and MP4 or WebM through a local
+//! `ffmpeg`, which can also make better GIFs with one palette for the whole animation.
+
+use image::{
+    Delay, Frame, ImageError,
+    codecs::gif::{GifEncoder, Repeat},
+};
+use tracing::{debug, info};
+
+use std::{
+    fmt,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    str::FromStr,
+};
+
+use super::{Headless, RenderOptions, render_png_frames};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Gif,
+    Mp4,
+    Webm,
+}
+
+impl Format {
+    /// Guess from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        extension.parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "gif" => Ok(Self::Gif),
+            "mp4" => Ok(Self::Mp4),
+            "webm" => Ok(Self::Webm),
+            _ => Err(format!(
+                "unknown format {s:?}, expected one of gif, mp4, webm"
+            )),
+        }
+    }
+}
+
+/// How many times an animation plays
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Loop {
+    #[default]
+    Forever,
+    /// At least once
+    Times(u16),
+}
+
+impl FromStr for Loop {
+    type Err = String;
+
+    /// `forever`, or a number of times from 1 up
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "forever" => Ok(Self::Forever),
+            times => match times.parse::<u16>() {
+                Ok(times) if times > 0 => Ok(Self::Times(times)),
+                _ => Err(format!(
+                    "bad loop count {s:?}, expected forever or a number from 1"
+                )),
+            },
+        }
+    }
+}
+
+/// How GIF colours get picked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Palette {
+    /// A palette for each frame, quantised as it's encoded.
+    /// `speed` runs from 1 (best colours) to 30 (quickest).
+    PerFrame { speed: i32 },
+    /// One palette for the whole animation, with dithering. Needs ffmpeg.
+    Global,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::PerFrame { speed: 10 }
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    /// `global`, `per-frame` or `per-frame:<speed>`, e.g. `per-frame:1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, speed) = match s.trim().split_once(':') {
+            Some((kind, speed)) => (kind.trim(), Some(speed.trim())),
+            None => (s.trim(), None),
+        };
+        match (kind, speed) {
+            ("global", None) => Ok(Self::Global),
+            ("per-frame", None) => Ok(Self::default()),
+            ("per-frame", Some(speed)) => match speed.parse::<i32>() {
+                Ok(speed) if (1..=30).contains(&speed) => Ok(Self::PerFrame { speed }),
+                _ => Err(format!(
+                    "bad palette speed {speed:?}, expected a number from 1 to 30"
+                )),
+            },
+            _ => Err(format!(
+                "unknown palette {s:?}, expected global, per-frame or per-frame:<speed>"
+            )),
+        }
+    }
+}
+
+pub struct ExportOptions {
+    /// What to render. `out_dir` is ignored.
+    pub render: RenderOptions,
+    pub output: PathBuf,
+    pub format: Format,
+    pub loops: Loop,
+    pub palette: Palette,
+}
+
+impl ExportOptions {
+    /// Parse `[options] <output>` from command line arguments: everything
+    /// [`RenderOptions::from_args`] takes, plus `--format <gif|mp4|webm>`,
+    /// `--loop <forever|times>` and `--palette <global|per-frame[:speed]>`.
+    /// The format is guessed from the output's extension if not given.
+    ///
+    /// ```
+    /// use klox::headless::export::{ExportOptions, Format, Loop};
+    ///
+    /// let args = ["--play", "slashies", "--loop", "3", "slashies.webm"];
+    /// let options = ExportOptions::from_args(args.map(String::from)).unwrap();
+    /// assert_eq!(options.format, Format::Webm);
+    /// assert_eq!(options.loops, Loop::Times(3));
+    ///
+    /// assert!(ExportOptions::from_args(["out.avi".to_string()]).is_err());
+    /// ```
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut render = RenderOptions::default();
+        let (mut output, mut format) = (None, None);
+        let (mut loops, mut palette) = (Loop::default(), Palette::default());
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                if output.replace(PathBuf::from(&arg)).is_some() {
+                    return Err(format!("more than one output given: {arg:?}"));
+                }
+                continue;
+            }
+            let value = args.next().ok_or_else(|| format!("{arg} needs a value"))?;
+            match arg.as_str() {
+                "--format" => format = Some(value.parse()?),
+                "--loop" => loops = value.parse()?,
+                "--palette" => palette = value.parse()?,
+                flag => {
+                    if !render.set(flag, value)? {
+                        return Err(format!("unknown option {flag}"));
+                    }
+                }
+            }
+        }
+        render.validate()?;
+
+        let output = output.ok_or("no output file given")?;
+        let format = match format {
+            Some(format) => format,
+            None => Format::from_path(&output).ok_or_else(|| {
+                format!("can't tell what format {output:?} should be, try --format")
+            })?,
+        };
+        Ok(Self {
+            render,
+            output,
+            format,
+            loops,
+            palette,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Image(ImageError),
+    /// Needed for this export, but not on the `PATH`
+    NoFfmpeg,
+    /// ffmpeg ran, but didn't succeed
+    Ffmpeg(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Image(e) => write!(f, "{e}"),
+            Self::NoFfmpeg => write!(
+                f,
+                "this export needs ffmpeg, which isn't installed or isn't on the PATH"
+            ),
+            Self::Ffmpeg(e) => write!(f, "ffmpeg failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<ImageError> for ExportError {
+    fn from(e: ImageError) -> Self {
+        Self::Image(e)
+    }
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Image(ImageError::IoError(e))
+    }
+}
+
+/// Whether there's an `ffmpeg` we can run
+pub fn ffmpeg_available() -> bool {
+    Command::new("ffmpeg")
+        .arg("-version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Render and encode the animation `options` describes
+pub fn export(options: &ExportOptions) -> Result<(), ExportError> {
+    match (options.format, options.palette) {
+        (Format::Gif, Palette::PerFrame { speed }) => export_gif(options, speed)?,
+        _ if !ffmpeg_available() => return Err(ExportError::NoFfmpeg),
+        _ => export_ffmpeg(options)?,
+    }
+    info!("exported {:?}", options.output);
+    Ok(())
+}
+
+/// Encode frames straight into a GIF as they're rendered
+fn export_gif(options: &ExportOptions, speed: i32) -> Result<(), ExportError> {
+    let file = BufWriter::new(File::create(&options.output)?);
+    let mut encoder = GifEncoder::new_with_speed(file, speed);
+    encoder.set_repeat(match options.loops {
+        Loop::Forever => Repeat::Infinite,
+        Loop::Times(times) => Repeat::Finite(times - 1),
+    })?;
+
+    let fps = options.render.fps as u64;
+    let mut headless = Headless::new(&options.render);
+    let mut shown_centis = 0;
+    for frame in 0..options.render.frames() as u64 {
+        // GIF delays are whole hundredths of a second, so round each frame's end time
+        // rather than its length, to stop the rounding adding up
+        let end_centis = ((frame + 1) * 100 + fps / 2) / fps;
+        let delay = Delay::from_numer_denom_ms(((end_centis - shown_centis) * 10) as u32, 1);
+        shown_centis = end_centis;
+        encoder.encode_frame(Frame::from_parts(headless.next_frame(), 0, 0, delay))?;
+    }
+    Ok(())
+}
+
+/// Render PNG frames somewhere temporary and have ffmpeg encode them
+fn export_ffmpeg(options: &ExportOptions) -> Result<(), ExportError> {
+    let frames_dir = std::env::temp_dir().join(format!("klox-export-{}", std::process::id()));
+    let render = RenderOptions {
+        out_dir: frames_dir.clone(),
+        ..options.render.clone()
+    };
+    let result = render_png_frames(&render)
+        .map_err(ExportError::from)
+        .and_then(|()| run_ffmpeg(options, &frames_dir));
+    if let Err(e) = std::fs::remove_dir_all(&frames_dir) {
+        debug!("couldn't clean up {frames_dir:?}: {e}");
+    }
+    result
+}
+
+fn run_ffmpeg(options: &ExportOptions, frames_dir: &Path) -> Result<(), ExportError> {
+    let mut ffmpeg = Command::new("ffmpeg");
+    ffmpeg
+        .args(["-y", "-loglevel", "error"])
+        .args(["-framerate", &options.render.fps.to_string()]);
+
+    // Videos don't loop by themselves, so play the frames over
+    if let (Format::Mp4 | Format::Webm, Loop::Times(times)) = (options.format, options.loops) {
+        ffmpeg.args(["-stream_loop", &(times - 1).to_string()]);
+    }
+    ffmpeg.arg("-i").arg(frames_dir.join("frame_%06d.png"));
+
+    match options.format {
+        Format::Gif => {
+            ffmpeg
+                .args([
+                    "-filter_complex",
+                    "[0:v] split [a][b]; [a] palettegen [p]; [b][p] paletteuse",
+                ])
+                .args([
+                    "-loop",
+                    &match options.loops {
+                        Loop::Forever => 0,
+                        Loop::Times(1) => -1,
+                        Loop::Times(times) => times as i32 - 1,
+                    }
+                    .to_string(),
+                ]);
+        }
+        // Odd sizes won't go into yuv420p
+        Format::Mp4 => {
+            ffmpeg.args([
+                "-vf",
+                "pad=ceil(iw/2)*2:ceil(ih/2)*2",
+                "-c:v",
+                "libx264",
+                "-pix_fmt",
+                "yuv420p",
+            ]);
+        }
+        Format::Webm => {
+            ffmpeg.args([
+                "-vf",
+                "pad=ceil(iw/2)*2:ceil(ih/2)*2",
+                "-c:v",
+                "libvpx-vp9",
+                "-pix_fmt",
+                "yuv420p",
+                "-b:v",
+                "0",
+                "-crf",
+                "32",
+            ]);
+        }
+    }
+    ffmpeg.arg(&options.output);
+
+    debug!("running {ffmpeg:?}");
+    let output = ffmpeg.stdin(Stdio::null()).output()?;
+    if !output.status.success() {
+        return Err(ExportError::Ffmpeg(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,262 @@
+//! Rendering without a window.
+//!
+//! [`Headless`] drives a [`Model`] with a fixed timestep and a [`ManualTime`], so
+//! frames come out the same however long they take to draw, and rasterises each one on the CPU.
+
+use image::{DynamicImage, ImageResult, Rgba, RgbaImage};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tracing::{debug, info};
+
+use std::{path::PathBuf, time::Duration};
+
+use crate::Drawable;
+use crate::assets::Background;
+use crate::clock::{Model, pattern::Pattern, time_source::ManualTime, timezone::Zone};
+use crate::config::Config;
+use crate::geom::Rect;
+
+pub mod export;
+pub mod raster;
+pub mod svg;
+
+use raster::{Canvas, Color};
+
+#[derive(Clone)]
+pub struct RenderOptions {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    /// How far into the animation to start rendering
+    pub skip: Duration,
+    /// How much time to render
+    pub duration: Duration,
+    /// Wall-clock time of the first frame, now if not given
+    pub start: Option<OffsetDateTime>,
+    /// IANA zone name to display the time in, as for [`Zone::resolve`]
+    pub zone: Option<String>,
+    /// Queued up to play once the clock has found the time
+    pub play: Option<Pattern>,
+    /// What to draw behind the clock, as [`Self::config`] says if not given
+    pub background: Option<Background>,
+    /// Everything else about how the clock looks and moves
+    pub config: Config,
+    /// Where to write frames
+    pub out_dir: PathBuf,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            width: 1280,
+            height: 480,
+            fps: 30,
+            skip: Duration::ZERO,
+            duration: Duration::from_secs(10),
+            start: None,
+            zone: None,
+            play: None,
+            background: None,
+            config: Config::default(),
+            out_dir: PathBuf::from("frames"),
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Parse options from command line arguments:
+    /// `--width <px>`, `--height <px>`, `--fps <n>`, `--skip <seconds>`, `--seconds <n>`,
+    /// `--start <RFC 3339 time>`, `--tz <zone>`, `--play <pattern>`,
+    /// `--background <path, "builtin" or "plain">`, `--out <dir>`
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or_else(|| format!("{flag} needs a value"))?;
+            if !options.set(&flag, value)? {
+                return Err(format!("unknown option {flag}"));
+            }
+        }
+        options.validate()?;
+        Ok(options)
+    }
+
+    /// Set the option for `flag` from `value`, or return `false` if `flag` isn't ours
+    pub fn set(&mut self, flag: &str, value: String) -> Result<bool, String> {
+        fn parse<T: std::str::FromStr>(flag: &str, value: String) -> Result<T, String>
+        where
+            T::Err: std::fmt::Display,
+        {
+            value
+                .parse()
+                .map_err(|e| format!("bad value {value:?} for {flag}: {e}"))
+        }
+        fn seconds(flag: &str, value: String) -> Result<Duration, String> {
+            let seconds = parse::<f64>(flag, value)?;
+            Duration::try_from_secs_f64(seconds).map_err(|e| format!("bad value for {flag}: {e}"))
+        }
+
+        match flag {
+            "--width" => self.width = parse(flag, value)?,
+            "--height" => self.height = parse(flag, value)?,
+            "--fps" => self.fps = parse(flag, value)?,
+            "--skip" => self.skip = seconds(flag, value)?,
+            "--seconds" => self.duration = seconds(flag, value)?,
+            "--start" => {
+                let start = OffsetDateTime::parse(&value, &Rfc3339)
+                    .map_err(|e| format!("bad value {value:?} for {flag}: {e}"))?;
+                self.start = Some(start);
+            }
+            "--tz" => self.zone = Some(value),
+            "--play" => self.play = Some(parse(flag, value)?),
+            "--background" => self.background = Some(parse(flag, value)?),
+            "--out" => self.out_dir = value.into(),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 || self.fps == 0 {
+            return Err("width, height and fps must all be more than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// How many frames it takes to skip [`Self::skip`]
+    pub fn skipped_frames(&self) -> u32 {
+        (self.skip.as_secs_f64() * self.fps as f64).round() as u32
+    }
+
+    /// How many frames [`Self::duration`] comes to
+    pub fn frames(&self) -> u32 {
+        (self.duration.as_secs_f64() * self.fps as f64).round() as u32
+    }
+}
+
+/// A [`Model`] and everything needed to step it along and draw it offscreen.
+///
+/// ```
+/// use klox::assets::Background;
+/// use klox::headless::{Headless, RenderOptions};
+///
+/// let options = RenderOptions {
+///     width: 640,
+///     height: 240,
+///     background: Some(Background::Plain),
+///     start: Some(time::macros::datetime!(2024-06-01 12:34:56 UTC)),
+///     zone: Some("UTC".to_string()),
+///     ..Default::default()
+/// };
+/// let mut headless = Headless::new(&options);
+/// let frame = headless.next_frame();
+///
+/// assert_eq!(frame.dimensions(), (640, 240));
+/// // White faces on a black background
+/// assert_eq!(frame.get_pixel(0, 0).0, [0, 0, 0, 255]);
+/// assert!(frame.pixels().any(|pixel| pixel.0 == [255, 255, 255, 255]));
+///
+/// // Straight onto the time, so it's the same every time
+/// assert_eq!(Headless::new(&options).next_frame(), frame);
+/// ```
+pub struct Headless {
+    model: Model,
+    time: ManualTime,
+    background: Option<DynamicImage>,
+    /// Behind the background, if there is one
+    fill: Color,
+    width: u32,
+    height: u32,
+    step: Duration,
+    frame: u32,
+}
+
+impl Headless {
+    pub fn new(options: &RenderOptions) -> Self {
+        let zone = match &options.zone {
+            Some(name) => Zone::resolve(Some(name)),
+            None => options.config.zone(),
+        };
+        let start = options.start.unwrap_or_else(OffsetDateTime::now_utc);
+        let time = ManualTime::new(start);
+        let mut model = Model::with_time(Box::new(time.clone()), zone);
+        model.configure(&options.config);
+        // Start on the time, rather than the random poses the hands start out in, so the same
+        // options always render the same frames
+        model.update(Duration::ZERO);
+        model.skip_to_destination();
+        if let Some(pattern) = options.play {
+            model.play(pattern);
+        }
+
+        let background = options
+            .background
+            .as_ref()
+            .unwrap_or(&options.config.background.image)
+            .image();
+
+        let mut headless = Self {
+            model,
+            time,
+            background,
+            fill: Rgba(options.config.background.color.0),
+            width: options.width,
+            height: options.height,
+            step: Duration::from_secs(1).div_f64(options.fps as f64),
+            frame: 0,
+        };
+        for _ in 0..options.skipped_frames() {
+            headless.step();
+        }
+        headless
+    }
+
+    /// For queueing up targets and timelines to render
+    pub fn model_mut(&mut self) -> &mut Model {
+        &mut self.model
+    }
+
+    /// Step everything along by one frame's worth of time and draw the result
+    pub fn next_frame(&mut self) -> RgbaImage {
+        self.step();
+        self.draw()
+    }
+
+    /// Step everything along by one frame's worth of time
+    pub fn step(&mut self) {
+        self.model.update(self.step * self.frame);
+        self.frame += 1;
+        self.time.step(self.step);
+    }
+
+    /// The model as it is now, as an SVG document
+    pub fn svg(&self) -> String {
+        let (w, h) = (self.width as f32, self.height as f32);
+        let mut svg = svg::Svg::new(w, h);
+        self.model.draw(Rect::from_w_h(w, h), &mut svg);
+        svg.finish()
+    }
+
+    /// Draw the model as it is now
+    pub fn draw(&self) -> RgbaImage {
+        let mut canvas = match &self.background {
+            Some(background) => Canvas::with_background(self.width, self.height, background),
+            None => Canvas::new(self.width, self.height, self.fill),
+        };
+        self.model.draw(canvas.rect(), &mut canvas);
+        canvas.into_image()
+    }
+}
+
+/// Render `options.frames()` frames to numbered PNGs in `options.out_dir`
+pub fn render_png_frames(options: &RenderOptions) -> ImageResult<()> {
+    std::fs::create_dir_all(&options.out_dir).map_err(image::ImageError::IoError)?;
+    let mut headless = Headless::new(options);
+    let frames = options.frames();
+    for frame in 0..frames {
+        let path = options.out_dir.join(format!("frame_{frame:06}.png"));
+        headless.next_frame().save(&path)?;
+        debug!("wrote {path:?}");
+    }
+    info!("wrote {frames} frames to {:?}", options.out_dir);
+    Ok(())
+}
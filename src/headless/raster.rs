@@ -0,0 +1,115 @@
+//! Just enough of a CPU rasteriser to draw clocklets: filled circles and thick lines, with
+//! a pixel of anti-aliasing at the edges.
+
+use image::{GenericImageView, Rgba, RgbaImage, imageops::FilterType};
+
+use crate::geom::{Point2, Rect, pt2, vec2};
+use crate::render::{self, Renderer};
+
+pub type Color = Rgba<u8>;
+
+/// An image to draw on, addressed in [`crate::geom`]'s coordinates: origin in the centre, y up
+pub struct Canvas {
+    image: RgbaImage,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32, fill: Color) -> Self {
+        Self {
+            image: RgbaImage::from_pixel(width, height, fill),
+        }
+    }
+
+    /// Fill the canvas with `background`, scaled to cover it and cropped to fit
+    pub fn with_background(width: u32, height: u32, background: &image::DynamicImage) -> Self {
+        let (w, h) = (background.width() as f32, background.height() as f32);
+        let scale = (width as f32 / w).max(height as f32 / h);
+        let (scaled_w, scaled_h) = (
+            ((w * scale).ceil() as u32).max(width),
+            ((h * scale).ceil() as u32).max(height),
+        );
+        let scaled = image::imageops::resize(background, scaled_w, scaled_h, FilterType::Triangle);
+        let image = image::imageops::crop_imm(
+            &scaled,
+            (scaled_w - width) / 2,
+            (scaled_h - height) / 2,
+            width,
+            height,
+        )
+        .to_image();
+        Self { image }
+    }
+
+    /// The whole canvas, in [`crate::geom`]'s coordinates
+    pub fn rect(&self) -> Rect {
+        Rect::from_w_h(self.image.width() as f32, self.image.height() as f32)
+    }
+
+    pub fn into_image(self) -> RgbaImage {
+        self.image
+    }
+
+    pub fn fill_circle(&mut self, centre: Point2, radius: f32, color: Color) {
+        let min = centre - vec2(radius, radius);
+        let max = centre + vec2(radius, radius);
+        self.shade(min, max, color, |p| radius - p.distance(centre));
+    }
+
+    /// A line `weight` pixels thick, with square ends flush with `start` and `end`
+    pub fn stroke_line(&mut self, start: Point2, end: Point2, weight: f32, color: Color) {
+        let half = weight / 2.0;
+        let length = start.distance(end);
+        if length == 0.0 {
+            return;
+        }
+        let along = (end - start) / length;
+        let min = start.min(end) - vec2(half, half);
+        let max = start.max(end) + vec2(half, half);
+        self.shade(min, max, color, |p| {
+            let rel = p - start;
+            let t = rel.dot(along);
+            let across = rel.perp_dot(along).abs();
+            (half - across).min(t).min(length - t)
+        });
+    }
+
+    /// Blend `color` over every pixel in the box from `min` to `max`, with coverage from
+    /// `inside`: how far the pixel centre is inside the shape, negative if outside
+    fn shade(&mut self, min: Point2, max: Point2, color: Color, inside: impl Fn(Point2) -> f32) {
+        let (w, h) = (self.image.width() as f32, self.image.height() as f32);
+        let x0 = (min.x + w / 2.0).floor().max(0.0) as u32;
+        let x1 = (max.x + w / 2.0).ceil().min(w) as u32;
+        let y0 = (h / 2.0 - max.y).floor().max(0.0) as u32;
+        let y1 = (h / 2.0 - min.y).ceil().min(h) as u32;
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let p = pt2(x as f32 + 0.5 - w / 2.0, h / 2.0 - y as f32 - 0.5);
+                let coverage = (inside(p) + 0.5).clamp(0.0, 1.0);
+                if coverage > 0.0 {
+                    blend(self.image.get_pixel_mut(x, y), color, coverage);
+                }
+            }
+        }
+    }
+}
+
+/// Faces and hands in whatever colors they're drawn in
+impl Renderer for Canvas {
+    fn face(&mut self, centre: Point2, radius: f32, color: render::Color) {
+        self.fill_circle(centre, radius, Rgba(color.0));
+    }
+
+    fn hand(&mut self, start: Point2, end: Point2, weight: f32, color: render::Color) {
+        self.stroke_line(start, end, weight, Rgba(color.0));
+    }
+}
+
+/// Source-over blend `color` onto `pixel`, scaled by `coverage`
+fn blend(pixel: &mut Color, color: Color, coverage: f32) {
+    let alpha = coverage * color[3] as f32 / 255.0;
+    for c in 0..3 {
+        pixel[c] = (color[c] as f32 * alpha + pixel[c] as f32 * (1.0 - alpha)).round() as u8;
+    }
+    pixel[3] = (255.0 * alpha + pixel[3] as f32 * (1.0 - alpha)).round() as u8;
+}
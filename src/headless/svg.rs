@@ -0,0 +1,225 @@
+//! Vector drawings of the clock: each face a circle and each hand a line, with the same
+//! geometry as `Drawable for Clocklet`.
+
+use std::{fmt::Write, path::PathBuf};
+
+use super::{Headless, RenderOptions};
+use crate::Drawable;
+use crate::clock::{Clock, Digit};
+use crate::geom::{Point2, Rect, pt2};
+use crate::render::{Color, Renderer};
+
+/// How big each clocklet's cell is in [`digits`] and [`font`], padding included
+pub const CLOCKLET_SIZE: f32 = 100.0;
+
+/// What to draw
+pub enum Drawing {
+    /// The clock as rendered by [`Headless`] after [`RenderOptions::skip`], see [`frame`]
+    Frame,
+    /// These digits side by side
+    Digits(Vec<Digit>),
+    /// Every digit, see [`font`]
+    Font,
+}
+
+pub struct SvgOptions {
+    /// For [`Drawing::Frame`]. `out_dir` is ignored.
+    pub render: RenderOptions,
+    pub drawing: Drawing,
+    pub output: PathBuf,
+}
+
+impl SvgOptions {
+    /// Parse `[options] <output>` from command line arguments: everything
+    /// [`RenderOptions::from_args`] takes, plus `--digits <digits>` (spaces for blanks) or
+    /// `--font` to draw those instead of a frame
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut render = RenderOptions::default();
+        let (mut drawing, mut output) = (Drawing::Frame, None);
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--font" => drawing = Drawing::Font,
+                "--digits" => {
+                    let value = args.next().ok_or("--digits needs a value")?;
+                    let digits = value
+                        .chars()
+                        .map(Digit::try_from)
+                        .collect::<Result<_, _>>()?;
+                    drawing = Drawing::Digits(digits);
+                }
+                flag if flag.starts_with("--") => {
+                    let value = args.next().ok_or_else(|| format!("{flag} needs a value"))?;
+                    if !render.set(flag, value)? {
+                        return Err(format!("unknown option {flag}"));
+                    }
+                }
+                _ => {
+                    if output.replace(PathBuf::from(&arg)).is_some() {
+                        return Err(format!("more than one output given: {arg:?}"));
+                    }
+                }
+            }
+        }
+        render.validate()?;
+
+        Ok(Self {
+            render,
+            drawing,
+            output: output.ok_or("no output file given")?,
+        })
+    }
+}
+
+/// Draw what `options` asks for and save it
+pub fn write(options: &SvgOptions) -> std::io::Result<()> {
+    let svg = match &options.drawing {
+        Drawing::Frame => frame(&options.render),
+        Drawing::Digits(digits) => self::digits(digits),
+        Drawing::Font => font(),
+    };
+    std::fs::write(&options.output, svg)
+}
+
+/// An SVG document of the first frame `options` renders, after [`RenderOptions::skip`]: the
+/// same as the first PNG `render` would write, hands caught mid-move and all
+///
+/// ```
+/// use klox::headless::{RenderOptions, svg};
+/// use std::time::Duration;
+///
+/// let options = RenderOptions {
+///     start: Some(time::macros::datetime!(2025-06-01 20:08:00 UTC)),
+///     zone: Some("UTC".to_string()),
+///     skip: Duration::from_millis(56_500),
+///     ..Default::default()
+/// };
+/// let drawing = svg::frame(&options);
+/// assert!(drawing.starts_with("<svg"));
+/// assert_eq!(svg::frame(&options), drawing);
+/// ```
+pub fn frame(options: &RenderOptions) -> String {
+    let mut headless = Headless::new(options);
+    headless.step();
+    headless.svg()
+}
+
+/// An SVG document of `clock` filling a `width` by `height` drawing, laid out as it would be
+/// in a window that size
+pub fn clock(clock: &Clock, width: f32, height: f32) -> String {
+    let mut svg = Svg::new(width, height);
+    clock.draw(Rect::from_w_h(width, height), &mut svg);
+    svg.finish()
+}
+
+/// An SVG document of `digits` side by side, e.g. a time
+///
+/// ```
+/// use klox::clock::Digit;
+/// use klox::headless::svg;
+///
+/// let svg = svg::digits(&[Digit::ONE, Digit::TWO]);
+/// assert!(svg.starts_with("<svg"));
+/// assert_eq!(svg.matches("<circle").count(), 12);
+/// assert_eq!(svg.matches("<line").count(), 24);
+/// ```
+pub fn digits(digits: &[Digit]) -> String {
+    let showing = Clock::showing(digits);
+    let size = showing.size();
+    clock(
+        &showing,
+        size.columns as f32 * CLOCKLET_SIZE,
+        size.rows as f32 * CLOCKLET_SIZE,
+    )
+}
+
+/// An SVG document of every [`Digit`], 0 to 9 then blank, with a clocklet's gap between each
+pub fn font() -> String {
+    let font: Vec<_> = (0..10).map(Digit::from).chain([Digit::BLANK]).collect();
+    let (digit_w, digit_h) = (
+        Digit::COLUMNS as f32 * CLOCKLET_SIZE,
+        Digit::ROWS as f32 * CLOCKLET_SIZE,
+    );
+    let step = digit_w + CLOCKLET_SIZE;
+    let width = step * font.len() as f32 - CLOCKLET_SIZE;
+
+    let mut svg = Svg::new(width, digit_h);
+    let left = -width / 2.0 + digit_w / 2.0;
+    for (i, digit) in font.iter().enumerate() {
+        let bounds = Rect::from_w_h(digit_w, digit_h).shift_x(left + i as f32 * step);
+        Clock::showing(std::slice::from_ref(digit)).draw(bounds, &mut svg);
+    }
+    svg.finish()
+}
+
+/// An SVG document being drawn on, `width` by `height`
+pub struct Svg {
+    width: f32,
+    height: f32,
+    body: String,
+}
+
+impl Svg {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            body: String::new(),
+        }
+    }
+
+    /// The finished document
+    pub fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">\n{body}</svg>\n",
+            w = num(self.width),
+            h = num(self.height),
+            body = self.body,
+        )
+    }
+
+    /// Our origin is in the middle with y up, SVG's is top left with y down
+    fn to_svg(&self, p: Point2) -> Point2 {
+        pt2(p.x + self.width / 2.0, self.height / 2.0 - p.y)
+    }
+}
+
+impl Renderer for Svg {
+    fn face(&mut self, centre: Point2, radius: f32, color: Color) {
+        let centre = self.to_svg(centre);
+        writeln!(
+            self.body,
+            r#"  <circle cx="{}" cy="{}" r="{}" fill="{color}"/>"#,
+            num(centre.x),
+            num(centre.y),
+            num(radius),
+        )
+        .unwrap();
+    }
+
+    fn hand(&mut self, start: Point2, end: Point2, weight: f32, color: Color) {
+        let (start, end) = (self.to_svg(start), self.to_svg(end));
+        writeln!(
+            self.body,
+            r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{color}" stroke-width="{}"/>"#,
+            num(start.x),
+            num(start.y),
+            num(end.x),
+            num(end.y),
+            num(weight),
+        )
+        .unwrap();
+    }
+}
+
+/// Short but precise enough
+fn num(n: f32) -> String {
+    let s = format!("{n:.2}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    match s {
+        "-0" => "0".to_string(),
+        s => s.to_string(),
+    }
+}
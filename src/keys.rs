@@ -0,0 +1,97 @@
+//! What the keys do, the same in every frontend.
+//!
+//! Each frontend turns its own key events into a [`Key`], looks up what it does with
+//! [`Action::for_key`] and applies that to its [`Model`], quitting in its own way.
+
+use crate::clock::{Digit, Model, pattern::Pattern};
+
+/// A key, as far as the clock's concerned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    /// A key that types something, e.g. `'q'`, `'='` or `' '`
+    Char(char),
+    Enter,
+    Backspace,
+    Escape,
+}
+
+/// Something a key does.
+///
+/// ```
+/// use klox::clock::{Model, time_source::ManualTime, timezone::Zone};
+/// use klox::keys::{Action, Key};
+/// use std::time::Duration;
+///
+/// assert_eq!(Action::for_key(Key::Char('q')), Some(Action::Quit));
+/// assert_eq!(Action::for_key(Key::Char('L')), Some(Action::Lap));
+/// assert_eq!(Action::for_key(Key::Enter), Some(Action::StartStop));
+/// assert_eq!(Action::for_key(Key::Char('?')), None);
+///
+/// let time = ManualTime::new(time::macros::datetime!(2025-06-01 12:00 UTC));
+/// let mut model = Model::with_time(Box::new(time), Zone::utc());
+/// model.time_with_stopwatch();
+/// Action::StartStop.apply(&mut model);
+/// model.update(Duration::from_secs(1));
+/// assert!(model.stopwatch().unwrap().is_running());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    /// Up to the frontend, [`Self::apply`] leaves it alone
+    Quit,
+    Play(Pattern),
+    ToggleChoreography,
+    ToggleResolution,
+    ShowDate,
+    StartStop,
+    ResetTimer,
+    ExtendCountdown,
+    Lap,
+    DismissAlarm,
+    SnoozeAlarm,
+    PushDigit(Digit),
+}
+
+impl Action {
+    /// What `key` does, if anything. Letters do the same whatever the case.
+    pub fn for_key(key: Key) -> Option<Self> {
+        let c = match key {
+            Key::Enter => return Some(Self::StartStop),
+            Key::Backspace => return Some(Self::ResetTimer),
+            Key::Escape => return Some(Self::Quit),
+            Key::Char(c) => c.to_ascii_lowercase(),
+        };
+        let action = match c {
+            'q' => Self::Quit,
+            'r' => Self::Play(Pattern::Scramble),
+            'c' => Self::ToggleChoreography,
+            's' => Self::ToggleResolution,
+            'd' => Self::ShowDate,
+            'l' => Self::Lap,
+            'x' => Self::DismissAlarm,
+            'z' => Self::SnoozeAlarm,
+            '=' => Self::ExtendCountdown,
+            '-' => Self::Play(Pattern::Horizontals),
+            '\\' => Self::Play(Pattern::Slashies),
+            c => Self::PushDigit(Digit::try_from(c).ok()?),
+        };
+        Some(action)
+    }
+
+    /// Do it to `model`
+    pub fn apply(self, model: &mut Model) {
+        match self {
+            Self::Quit => {}
+            Self::Play(pattern) => model.play(pattern),
+            Self::ToggleChoreography => model.toggle_choreography(),
+            Self::ToggleResolution => model.toggle_resolution(),
+            Self::ShowDate => model.show_date(),
+            Self::StartStop => model.start_stop(),
+            Self::ResetTimer => model.reset_timer(),
+            Self::ExtendCountdown => model.extend_countdown(),
+            Self::Lap => model.lap(),
+            Self::DismissAlarm => model.dismiss_alarm(),
+            Self::SnoozeAlarm => model.snooze_alarm(),
+            Self::PushDigit(digit) => model.push_debug_digit(&digit),
+        }
+    }
+}
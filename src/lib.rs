@@ -1,17 +1,28 @@
-use nannou::prelude::*;
-
+pub mod assets;
+#[cfg(feature = "bevy")]
+pub mod bevy_app;
 pub mod clock;
+pub mod config;
+pub mod geom;
+pub mod headless;
+pub mod keys;
+pub mod render;
+pub mod terminal;
+pub mod watch;
+pub mod window;
+
+use geom::Rect;
+use render::Renderer;
 
 pub trait Drawable {
-    fn draw(&self, bounds: Rect, draw: &Draw);
-    fn update(&mut self, _update: &Update) {}
+    fn draw(&self, bounds: Rect, renderer: &mut dyn Renderer);
 }
 
 pub trait RectUtils: Sized + Copy {
     /// Takes a and returns 2 sub-Rects split down the y-axis
     /// ```
-    /// use nannou::prelude::*;
     /// use klox::RectUtils;
+    /// use klox::geom::Rect;
     ///
     /// let rect = Rect::from_w_h(100.0, 100.0);
     /// let (left, right) = rect.split_y_axis();
@@ -24,8 +35,8 @@ pub trait RectUtils: Sized + Copy {
     /// Takes rows, cols and slices a Rect up evenly
     ///
     /// ```
-    /// use nannou::prelude::*;
     /// use klox::RectUtils;
+    /// use klox::geom::Rect;
     ///
     /// let rect = Rect::from_w_h(80.0, 30.0);
     ///
@@ -49,13 +60,28 @@ pub trait RectUtils: Sized + Copy {
     /// assert_eq!(grid[3][2], Rect::from_corner_points([20.0, -5.0], [40.0, -15.0]));
     /// ```
     fn grid<const C: usize, const R: usize>(self) -> [[Self; R]; C];
+
+    /// Like [`RectUtils::grid`], for when the number of columns and rows is only known at runtime
+    ///
+    /// ```
+    /// use klox::RectUtils;
+    /// use klox::geom::Rect;
+    ///
+    /// let rect = Rect::from_w_h(120.0, 30.0);
+    /// let grid = rect.grid_of(12, 3);
+    ///
+    /// assert_eq!(grid.len(), 12);
+    /// assert_eq!(grid[11].len(), 3);
+    /// assert_eq!(grid[11][2], Rect::from_corner_points([50.0, -5.0], [60.0, -15.0]));
+    /// ```
+    fn grid_of(self, cols: usize, rows: usize) -> Vec<Vec<Self>>;
 }
 
 impl RectUtils for Rect {
     fn split_y_axis(self) -> (Self, Self) {
         (
-            Rect::from_corners(self.mid_bottom(), self.top_left()),
-            Rect::from_corners(self.mid_top(), self.bottom_right()),
+            Rect::from_corner_points([self.left(), self.top()], [self.xy().x, self.bottom()]),
+            Rect::from_corner_points([self.xy().x, self.top()], [self.right(), self.bottom()]),
         )
     }
 
@@ -74,4 +100,24 @@ impl RectUtils for Rect {
             })
         })
     }
+
+    fn grid_of(self, cols: usize, rows: usize) -> Vec<Vec<Self>> {
+        let (l, t, w, h) = self.l_t_w_h();
+        let col_width = w / cols as f32;
+        let row_height = h / rows as f32;
+        (0..cols)
+            .map(|c| {
+                let c = c as f32;
+                (0..rows)
+                    .map(|r| {
+                        let r = r as f32;
+                        Rect::from_corner_points(
+                            [l + c * col_width, t - r * row_height],
+                            [l + (c + 1.0) * col_width, t - (r + 1.0) * row_height],
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
 }
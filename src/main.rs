@@ -1,35 +1,186 @@
-use klox::Drawable;
-use klox::clock::{Model, app};
-use nannou::prelude::*;
+use klox::clock::pattern::Pattern;
+use klox::config::{self, Config};
+use klox::headless::{
+    RenderOptions,
+    export::{ExportOptions, export},
+    render_png_frames,
+    svg::{self, SvgOptions},
+};
+use klox::terminal::{self, TerminalOptions};
+use klox::window::{self, WindowOptions};
+use tracing_subscriber::filter::LevelFilter;
+
+use std::{fs::File, path::PathBuf, sync::Mutex};
+
+const USAGE: &str = "\
+usage: klox [--config <path>] [--log-level <level>] [--log-file <path>] [<command>] [<options>]
+
+commands:
+  run              tell the time in a window (the default)
+                     --fullscreen, --size <width>x<height>
+  show <digits>    hold digits still in a window instead, e.g. 12:34, with spaces for blanks
+                     and run's options
+  countdown <duration>
+                   count down in a window instead, from [[HH:]MM:]SS, e.g. 25:00, with run's
+                     options. Return starts and pauses, Backspace resets and = adds a minute.
+  stopwatch        time with a stopwatch in a window instead, with run's options. Return
+                     starts and stops, Backspace resets and l holds a lap for a moment.
+  render           write PNG frames
+                     --width <px>, --height <px>, --fps <n>, --skip <seconds>,
+                     --seconds <n>, --start <RFC 3339 time>, --tz <zone>, --play <pattern>,
+                     --background <path, builtin or plain>, --out <dir>
+  export <output>  write a GIF, MP4 or WebM, with render's options and
+                     --format <gif|mp4|webm>, --loop <forever|times>,
+                     --palette <global|per-frame[:speed]>
+  svg <output>     write an SVG of a frame, with render's options, or of
+                     --digits <digits> or --font
+  terminal         tell the time in the terminal
+                     --cell-aspect <height/width>, --fps <n>, --countdown <duration>,
+                     --stopwatch
+  list-patterns    everything --play takes
+  help             this
+
+--config defaults to KLOX_CONFIG, or config.toml in ~/.config/klox. --log-level is off, error,
+warn, info (the default), debug or trace. Logs go to stdout, or to --log-file if it's given. In
+the terminal there's nowhere to show them without drawing over the clock, so they're off there
+unless --log-file is given. These can all go before or after the command.";
 
 fn main() {
-    tracing_subscriber::fmt::init();
-    app().simple_window(view).run();
-}
+    let mut args: Vec<_> = std::env::args().skip(1).collect();
+    let options = global_options(&mut args).unwrap_or_else(|e| usage("", e));
+    let config_path = options.config;
+
+    let mut args = args.into_iter();
+    let command = args.next();
+    let subscriber = tracing_subscriber::fmt().with_max_level(options.log_level);
+    match options.log_file {
+        Some(path) => {
+            let file = File::create(&path)
+                .unwrap_or_else(|e| fail("log-file", format!("couldn't create {path:?}: {e}")));
+            subscriber
+                .with_writer(Mutex::new(file))
+                .with_ansi(false)
+                .init();
+        }
+        // stdout and stderr are both the screen the clock's drawn on
+        None if command.as_deref() == Some("terminal") => {
+            subscriber.with_max_level(LevelFilter::OFF).init();
+        }
+        None => subscriber.init(),
+    }
 
-fn view(app: &App, model: &Model, frame: Frame) {
-    // // get canvas to draw on
-    let draw = app.draw();
+    // Given explicitly, it has to be there
+    let config = match &config_path {
+        Some(path) => Config::load(path),
+        None => Config::load_default(),
+    }
+    .unwrap_or_else(|e| fail("config", e));
+    let config_path = config_path.or_else(config::path);
 
-    let win = app.window_rect();
-    let (win_w, win_h) = (win.w(), win.h());
+    match command.as_deref() {
+        None | Some("run") => {
+            let options = WindowOptions::from_args(args).unwrap_or_else(|e| usage("run", e));
+            window::run(config, config_path, options);
+        }
+        Some("show") => {
+            let options = WindowOptions::show_from_args(args).unwrap_or_else(|e| usage("show", e));
+            window::run(config, config_path, options);
+        }
+        Some("countdown") => {
+            let options =
+                WindowOptions::countdown_from_args(args).unwrap_or_else(|e| usage("countdown", e));
+            window::run(config, config_path, options);
+        }
+        Some("stopwatch") => {
+            let options = WindowOptions {
+                stopwatch: true,
+                ..WindowOptions::from_args(args).unwrap_or_else(|e| usage("stopwatch", e))
+            };
+            window::run(config, config_path, options);
+        }
+        Some("render") => {
+            let mut options = RenderOptions::from_args(args).unwrap_or_else(|e| usage("render", e));
+            options.config = config;
+            if let Err(e) = render_png_frames(&options) {
+                fail("render", e);
+            }
+        }
+        Some("export") => {
+            let mut options = ExportOptions::from_args(args).unwrap_or_else(|e| usage("export", e));
+            options.render.config = config;
+            if let Err(e) = export(&options) {
+                fail("export", e);
+            }
+        }
+        Some("svg") => {
+            let mut options = SvgOptions::from_args(args).unwrap_or_else(|e| usage("svg", e));
+            options.render.config = config;
+            if let Err(e) = svg::write(&options) {
+                fail("svg", e);
+            }
+        }
+        Some("terminal") => {
+            let options = TerminalOptions::from_args(args).unwrap_or_else(|e| usage("terminal", e));
+            if let Err(e) = terminal::run(&options, &config, config_path) {
+                fail("terminal", e);
+            }
+        }
+        Some("list-patterns") => {
+            for pattern in Pattern::ALL {
+                println!("{pattern}");
+            }
+        }
+        Some("help" | "--help" | "-h") => println!("{USAGE}"),
+        Some(command) => usage("", format!("unknown command {command:?}")),
+    }
+}
+
+/// Options for every command
+struct GlobalOptions {
+    config: Option<PathBuf>,
+    log_level: LevelFilter,
+    log_file: Option<PathBuf>,
+}
 
-    // --- COVER FIT (fill & crop; preserves aspect ratio) ---
-    // Scale so the smaller axis fits, then crop the overflow.
-    let sx = win_w / model.background_width;
-    let sy = win_h / model.background_height;
-    let scale = sx.max(sy); // cover
-    let target_w = model.background_width * scale;
-    let target_h = model.background_height * scale;
+/// Take `--config <path>`, `--log-level <level>` and `--log-file <path>` out of `args`
+fn global_options(args: &mut Vec<String>) -> Result<GlobalOptions, String> {
+    let config = take_option(args, "--config")?.map(PathBuf::from);
+    let log_level = match take_option(args, "--log-level")? {
+        Some(level) => level
+            .parse()
+            .map_err(|e| format!("bad value {level:?} for --log-level: {e}"))?,
+        None => LevelFilter::INFO,
+    };
+    let log_file = take_option(args, "--log-file")?.map(PathBuf::from);
+    Ok(GlobalOptions {
+        config,
+        log_level,
+        log_file,
+    })
+}
 
-    // set background image
-    draw.texture(&model.background)
-        .xy(win.xy()) // center in the window
-        .w_h(target_w, target_h);
+/// Remove `flag` and its value from wherever they are in `args`, returning the value
+fn take_option(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    let Some(i) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+    if i + 1 == args.len() {
+        return Err(format!("{flag} needs a value"));
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(value))
+}
 
-    // render our Model
-    model.draw(win, &draw);
+fn usage(command: &str, e: String) -> ! {
+    match command {
+        "" => eprintln!("klox: {e}\n\n{USAGE}"),
+        _ => eprintln!("klox {command}: {e}"),
+    }
+    std::process::exit(2);
+}
 
-    // put everything on the frame
-    draw.to_frame(app, &frame).unwrap();
+fn fail(command: &str, e: impl std::fmt::Display) -> ! {
+    eprintln!("klox {command}: {e}");
+    std::process::exit(1);
 }
@@ -0,0 +1,113 @@
+//! Drawing clocks, whatever onto.
+//!
+//! The clock works out where every face and hand goes, and a [`Renderer`] draws them, so each
+//! backend gets exactly the same geometry.
+
+use std::{fmt, str::FromStr};
+
+use crate::geom::Point2;
+
+/// Something clocks can be drawn on: a window, an image, a terminal, a document...
+///
+/// Everything's in [`crate::geom`]'s coordinates. A clocklet's face is always drawn before its
+/// hands.
+///
+/// ```
+/// use klox::Drawable;
+/// use klox::clock::Clock;
+/// use klox::geom::{Point2, Rect};
+/// use klox::render::{Color, Renderer};
+///
+/// #[derive(Default)]
+/// struct Count {
+///     faces: usize,
+///     hands: usize,
+/// }
+///
+/// impl Renderer for Count {
+///     fn face(&mut self, _centre: Point2, _radius: f32, _color: Color) {
+///         self.faces += 1;
+///     }
+///
+///     fn hand(&mut self, _start: Point2, _end: Point2, _weight: f32, _color: Color) {
+///         self.hands += 1;
+///     }
+/// }
+///
+/// let mut count = Count::default();
+/// Clock::default().draw(Rect::from_w_h(800.0, 300.0), &mut count);
+/// assert_eq!((count.faces, count.hands), (24, 48));
+/// ```
+pub trait Renderer {
+    /// A clocklet's face: a circle
+    fn face(&mut self, centre: Point2, radius: f32, color: Color);
+
+    /// One of a clocklet's hands: a line `weight` pixels thick
+    fn hand(&mut self, start: Point2, end: Point2, weight: f32, color: Color);
+}
+
+/// An sRGB colour: red, green, blue and alpha
+///
+/// ```
+/// use klox::render::Color;
+///
+/// assert_eq!("#ff8000".parse(), Ok(Color([255, 128, 0, 255])));
+/// assert_eq!("#f80".parse(), Ok(Color([255, 136, 0, 255])));
+/// assert_eq!("#00000080".parse(), Ok(Color([0, 0, 0, 128])));
+/// assert_eq!("white".parse(), Ok(Color::WHITE));
+/// assert_eq!(Color([255, 128, 0, 255]).to_string(), "#ff8000");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color(pub [u8; 4]);
+
+impl Color {
+    pub const WHITE: Self = Self([255, 255, 255, 255]);
+    pub const BLACK: Self = Self([0, 0, 0, 255]);
+
+    pub fn alpha(self) -> u8 {
+        self.0[3]
+    }
+}
+
+/// `#rrggbb`, or `#rrggbbaa` if it's at all transparent
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [r, g, b, a] = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}")?;
+        if a != 255 {
+            write!(f, "{a:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    /// Parse `#rgb`, `#rrggbb`, `#rrggbbaa`, `white` or `black`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || format!("bad color {s:?}, expected #rgb, #rrggbb, #rrggbbaa, white or black");
+        let hex = match s.trim() {
+            "white" => return Ok(Self::WHITE),
+            "black" => return Ok(Self::BLACK),
+            s => s.strip_prefix('#').ok_or_else(bad)?,
+        };
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(bad());
+        }
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|c| c.to_digit(16).unwrap_or_default() as u8)
+            .collect();
+        let channels: Vec<u8> = match digits.len() {
+            3 => digits.iter().map(|d| d * 17).collect(),
+            6 | 8 => digits
+                .chunks(2)
+                .map(|pair| pair[0] * 16 + pair[1])
+                .collect(),
+            _ => return Err(bad()),
+        };
+        let alpha = channels.get(3).copied().unwrap_or(255);
+        Ok(Self([channels[0], channels[1], channels[2], alpha]))
+    }
+}
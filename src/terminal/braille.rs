@@ -0,0 +1,146 @@
+//! Drawing with braille characters, each of which is a block of 2 dots across by 4 down.
+
+use crate::geom::{Point2, Rect, Vec2, pt2, vec2};
+use crate::render::{Color, Renderer};
+
+/// How wide a character cell is in the made-up pixels that layout happens in. Layout padding is
+/// in pixels, so this keeps a terminal's clock looking like a window's of about the same size.
+const CELL_WIDTH: f32 = 10.0;
+
+/// A grid of dots the size of a terminal, addressed in [`crate::geom`]'s coordinates (origin in
+/// the centre, y up) as if each character cell were [`CELL_WIDTH`] pixels wide.
+///
+/// ```
+/// use klox::geom::{Rect, pt2};
+/// use klox::terminal::braille::Braille;
+///
+/// let mut braille = Braille::new(2, 1, 2.0);
+/// assert_eq!(braille.rect(), Rect::from_w_h(20.0, 20.0));
+///
+/// // A line across the top of the left cell
+/// braille.stroke_line(pt2(-10.0, 9.0), pt2(0.0, 9.0));
+/// assert_eq!(braille.lines(), ["⠉ "]);
+/// ```
+pub struct Braille {
+    columns: usize,
+    rows: usize,
+    /// How tall each character cell is for its width
+    cell_aspect: f32,
+    /// Row by row
+    dots: Vec<bool>,
+}
+
+impl Braille {
+    pub fn new(columns: usize, rows: usize, cell_aspect: f32) -> Self {
+        Self {
+            columns,
+            rows,
+            cell_aspect,
+            dots: vec![false; columns * 2 * rows * 4],
+        }
+    }
+
+    /// The whole grid, in [`crate::geom`]'s coordinates
+    pub fn rect(&self) -> Rect {
+        Rect::from_w_h(
+            self.columns as f32 * CELL_WIDTH,
+            self.rows as f32 * CELL_WIDTH * self.cell_aspect,
+        )
+    }
+
+    /// How big a dot is, across and down
+    fn dot_size(&self) -> Vec2 {
+        vec2(CELL_WIDTH / 2.0, CELL_WIDTH * self.cell_aspect / 4.0)
+    }
+
+    /// How thick outlines are: about a dot, whichever way they go
+    fn thickness(&self) -> f32 {
+        let dot = self.dot_size();
+        dot.x.max(dot.y)
+    }
+
+    pub fn stroke_circle(&mut self, centre: Point2, radius: f32) {
+        let half = self.thickness() / 2.0;
+        let reach = vec2(radius + half, radius + half);
+        self.plot(centre - reach, centre + reach, |p| {
+            (p.distance(centre) - radius).abs() <= half
+        });
+    }
+
+    pub fn stroke_line(&mut self, start: Point2, end: Point2) {
+        let half = self.thickness() / 2.0;
+        let length = start.distance(end);
+        let along = if length > 0.0 {
+            (end - start) / length
+        } else {
+            Vec2::ZERO
+        };
+        let reach = vec2(half, half);
+        self.plot(start.min(end) - reach, start.max(end) + reach, |p| {
+            let rel = p - start;
+            let t = rel.dot(along).clamp(0.0, length);
+            rel.distance(along * t) <= half
+        });
+    }
+
+    /// Set every dot in the box from `min` to `max` whose centre is `inside` the shape
+    fn plot(&mut self, min: Point2, max: Point2, inside: impl Fn(Point2) -> bool) {
+        let dot = self.dot_size();
+        let (w, h) = (self.columns * 2, self.rows * 4);
+        let half = self.rect().wh() / 2.0;
+        let x0 = ((min.x + half.x) / dot.x).floor().max(0.0) as usize;
+        let x1 = (((max.x + half.x) / dot.x).ceil() as usize).min(w);
+        let y0 = ((half.y - max.y) / dot.y).floor().max(0.0) as usize;
+        let y1 = (((half.y - min.y) / dot.y).ceil() as usize).min(h);
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let p = pt2(
+                    (x as f32 + 0.5) * dot.x - half.x,
+                    half.y - (y as f32 + 0.5) * dot.y,
+                );
+                if inside(p) {
+                    self.dots[y * w + x] = true;
+                }
+            }
+        }
+    }
+
+    /// One string per row of character cells
+    pub fn lines(&self) -> Vec<String> {
+        // Which bit each dot sets in a cell's character, by dot row then column
+        const BITS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+        let w = self.columns * 2;
+        (0..self.rows)
+            .map(|row| {
+                (0..self.columns)
+                    .map(|col| {
+                        let mut bits = 0;
+                        for (dy, row_bits) in BITS.iter().enumerate() {
+                            for (dx, bit) in row_bits.iter().enumerate() {
+                                if self.dots[(row * 4 + dy) * w + col * 2 + dx] {
+                                    bits |= bit;
+                                }
+                            }
+                        }
+                        match bits {
+                            0 => ' ',
+                            bits => char::from_u32(0x2800 + bits).unwrap_or(' '),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Everything in outline, about a dot thick and whatever the color, since there's only on and off
+impl Renderer for Braille {
+    fn face(&mut self, centre: Point2, radius: f32, _color: Color) {
+        self.stroke_circle(centre, radius);
+    }
+
+    fn hand(&mut self, start: Point2, end: Point2, _weight: f32, _color: Color) {
+        self.stroke_line(start, end);
+    }
+}
@@ -0,0 +1,180 @@
+//! Running klox in a terminal, drawn in braille, with no window and no nannou app.
+//!
+//! The [`Model`] is the same one the window uses, so the keys do the same things too.
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use tracing::warn;
+
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::Drawable;
+use crate::clock::{Model, countdown::parse_duration};
+use crate::config::Config;
+use crate::keys::{Action, Key};
+use crate::watch::Watcher;
+
+pub mod braille;
+
+use braille::Braille;
+
+pub struct TerminalOptions {
+    /// How tall a character cell is for its width. Most terminal fonts are about 2.
+    pub cell_aspect: f32,
+    pub fps: u32,
+    /// Counted down from instead of telling the time, see [`Model::count_down`]
+    pub countdown: Option<Duration>,
+    /// Timing with a stopwatch instead of telling the time
+    pub stopwatch: bool,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        Self {
+            cell_aspect: 2.0,
+            fps: 30,
+            countdown: None,
+            stopwatch: false,
+        }
+    }
+}
+
+impl TerminalOptions {
+    /// Parse options from command line arguments: `--cell-aspect <height/width>`, `--fps <n>`,
+    /// `--countdown <duration>` (as for [`parse_duration`]), `--stopwatch`
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            if flag == "--stopwatch" {
+                options.stopwatch = true;
+                continue;
+            }
+            let value = args.next().ok_or_else(|| format!("{flag} needs a value"))?;
+            let bad_value = |e| format!("bad value {value:?} for {flag}: {e}");
+            match flag.as_str() {
+                "--cell-aspect" => {
+                    options.cell_aspect = value.parse().map_err(|e| bad_value(format!("{e}")))?;
+                }
+                "--fps" => options.fps = value.parse().map_err(|e| bad_value(format!("{e}")))?,
+                "--countdown" => {
+                    options.countdown = Some(parse_duration(&value).map_err(bad_value)?)
+                }
+                _ => return Err(format!("unknown option {flag}")),
+            }
+        }
+        if !(options.cell_aspect > 0.0 && options.cell_aspect.is_finite()) {
+            return Err("cell aspect must be more than 0".to_string());
+        }
+        if options.fps == 0 {
+            return Err("fps must be more than 0".to_string());
+        }
+        Ok(options)
+    }
+}
+
+/// Takes over the terminal while it's around, and puts it back as it was when dropped
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            terminal::EnterAlternateScreen,
+            cursor::Hide,
+            terminal::Clear(ClearType::All)
+        )?;
+        Ok(Self)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Tell the time in the terminal until `q`, `Esc` or `Ctrl-C`, reloading `config` from
+/// `config_path` whenever that changes
+pub fn run(
+    options: &TerminalOptions,
+    config: &Config,
+    config_path: Option<PathBuf>,
+) -> io::Result<()> {
+    let mut model = Model::from_config(config);
+    if let Some(duration) = options.countdown {
+        model.count_down(duration);
+    }
+    if options.stopwatch {
+        model.time_with_stopwatch();
+    }
+    // No assets to reload in here, just the config
+    let watcher = Watcher::new(config_path, None)
+        .map_err(|e| warn!("not watching for changes: {e}"))
+        .ok();
+    let frame = Duration::from_secs(1) / options.fps;
+
+    let _screen = Screen::enter()?;
+    let mut stdout = io::stdout();
+    let began = Instant::now();
+    loop {
+        while event::poll(Duration::ZERO)? {
+            match event::read()? {
+                Event::Key(key)
+                    if key.kind != KeyEventKind::Release && !handle_key(&mut model, key) =>
+                {
+                    return Ok(());
+                }
+                Event::Resize(..) => queue!(stdout, terminal::Clear(ClearType::All))?,
+                _ => {}
+            }
+        }
+
+        if let Some(config) = watcher.as_ref().and_then(|w| w.changes().config) {
+            model.configure(&config);
+        }
+
+        let now = Instant::now();
+        model.update(now - began);
+
+        let (columns, rows) = terminal::size()?;
+        let mut braille = Braille::new(columns as usize, rows as usize, options.cell_aspect);
+        model.draw(braille.rect(), &mut braille);
+        for (row, line) in braille.lines().into_iter().enumerate() {
+            queue!(stdout, cursor::MoveTo(0, row as u16), Print(line))?;
+        }
+        stdout.flush()?;
+
+        // Sleep until the next frame, unless a key wakes us up
+        event::poll(frame.saturating_sub(now.elapsed()))?;
+    }
+}
+
+/// The keys, as in [`crate::keys`]. Returns `false` to quit.
+fn handle_key(model: &mut Model, key: KeyEvent) -> bool {
+    let key = match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Esc => Key::Escape,
+        _ => return true,
+    };
+    match Action::for_key(key) {
+        Some(Action::Quit) => return false,
+        Some(action) => action.apply(model),
+        None => {}
+    }
+    true
+}
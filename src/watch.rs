@@ -0,0 +1,122 @@
+//! Noticing when the config file or the assets change, so they can be applied without
+//! restarting.
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use tracing::{debug, error, info, warn};
+
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+};
+
+use crate::config::Config;
+
+/// Watches the config file and the assets directory
+///
+/// ```
+/// use klox::watch::Watcher;
+/// use std::time::{Duration, Instant};
+///
+/// let dir = std::env::temp_dir().join(format!("klox-watch-{}", std::process::id()));
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("config.toml");
+/// let watcher = Watcher::new(Some(path.clone()), None).unwrap();
+///
+/// std::fs::write(&path, "[layout]\nhand_weight = 9").unwrap();
+/// let began = Instant::now();
+/// // There might be a change or two noticed halfway through writing
+/// while watcher.changes().config.map(|c| c.layout.hand_weight) != Some(9.0) {
+///     assert!(began.elapsed() < Duration::from_secs(5), "no change noticed");
+///     std::thread::sleep(Duration::from_millis(10));
+/// }
+/// std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct Watcher {
+    /// Stops watching when dropped
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+    config_path: Option<PathBuf>,
+    assets_dir: Option<PathBuf>,
+}
+
+/// What's changed since last time
+#[derive(Default)]
+pub struct Changes {
+    /// The config, reloaded, if its file changed and it still loads
+    pub config: Option<Config>,
+    /// Something in the assets directory
+    pub assets: bool,
+}
+
+impl Watcher {
+    /// Watch the file at `config_path` and everything in `assets_dir`, if they're given.
+    ///
+    /// The config file's directory is what's actually watched, since plenty of editors save by
+    /// replacing the file rather than writing to it. The file needn't exist yet, as long as
+    /// its directory does.
+    pub fn new(config_path: Option<PathBuf>, assets_dir: Option<PathBuf>) -> notify::Result<Self> {
+        // Events come with absolute paths
+        let absolute = |path: PathBuf| std::path::absolute(&path).unwrap_or(path);
+        let (config_path, assets_dir) = (config_path.map(absolute), assets_dir.map(absolute));
+
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        let config_dir = config_path.as_deref().and_then(Path::parent);
+        for dir in [config_dir, assets_dir.as_deref()].into_iter().flatten() {
+            match watcher.watch(dir, RecursiveMode::NonRecursive) {
+                Ok(()) => debug!("watching {}", dir.display()),
+                Err(e) => warn!("can't watch {} for changes: {e}", dir.display()),
+            }
+        }
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            config_path,
+            assets_dir,
+        })
+    }
+
+    /// Everything that's changed since the last call, without waiting. A config that no longer
+    /// loads is logged and left out, so whatever's running can carry on with the last good one.
+    pub fn changes(&self) -> Changes {
+        let (mut config_changed, mut assets) = (false, false);
+        for event in self.events.try_iter() {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    warn!("error watching for changes: {e}");
+                    continue;
+                }
+            };
+            // Not reads, or we'd notice ourselves reloading
+            if !matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                continue;
+            }
+            for path in &event.paths {
+                config_changed |= self.config_path.as_deref() == Some(path.as_path());
+                assets |= self.assets_dir.as_deref() == path.parent();
+            }
+        }
+
+        let config = config_changed
+            .then_some(self.config_path.as_deref())
+            .flatten()
+            .and_then(|path| {
+                let config = match path.exists() {
+                    true => Config::load(path),
+                    false => Ok(Config::default().with_env()),
+                };
+                config
+                    .inspect(|_| info!("reloaded {}", path.display()))
+                    .map_err(|e| error!("keeping the last good config: {e}"))
+                    .ok()
+            });
+        if assets {
+            info!("assets changed, reloading");
+        }
+        Changes { config, assets }
+    }
+}
@@ -0,0 +1,304 @@
+//! The clock in a nannou window, over a background image.
+
+use nannou::image::GenericImageView;
+use nannou::prelude::*;
+use tracing::warn;
+
+use std::{path::PathBuf, sync::OnceLock, time::Duration};
+
+use crate::Drawable;
+use crate::assets;
+use crate::clock::{Digit, Model, countdown::parse_duration};
+use crate::config::{self, Config};
+use crate::geom;
+use crate::keys::{self, Action};
+use crate::render::{self, Renderer};
+use crate::watch::Watcher;
+
+/// Handed over by [`run`], since nannou's model function can't take anything but the app
+static STARTUP: OnceLock<Startup> = OnceLock::new();
+
+struct Startup {
+    config: Config,
+    config_path: Option<PathBuf>,
+    options: WindowOptions,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WindowOptions {
+    pub fullscreen: bool,
+    /// Width and height in points, nannou's default if not given
+    pub size: Option<(u32, u32)>,
+    /// Held still instead of telling the time, see [`Model::freeze`]
+    pub show: Option<Vec<Digit>>,
+    /// Counted down from instead of telling the time, see [`Model::count_down`]
+    pub countdown: Option<Duration>,
+    /// Timing with a stopwatch instead of telling the time
+    pub stopwatch: bool,
+}
+
+impl WindowOptions {
+    /// Parse options from command line arguments: `--fullscreen`, `--size <width>x<height>`
+    ///
+    /// ```
+    /// use klox::window::WindowOptions;
+    ///
+    /// let args = ["--size", "800x300", "--fullscreen"].map(String::from);
+    /// let options = WindowOptions::from_args(args).unwrap();
+    /// assert_eq!(options.size, Some((800, 300)));
+    /// assert!(options.fullscreen);
+    ///
+    /// let options = WindowOptions::show_from_args(["12:34".to_string()]).unwrap();
+    /// assert_eq!(options.show.map(|digits| digits.len()), Some(4));
+    ///
+    /// assert!(WindowOptions::from_args(["--size", "800"].map(String::from)).is_err());
+    /// assert!(WindowOptions::show_from_args(["12h34".to_string()]).is_err());
+    /// ```
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--fullscreen" => options.fullscreen = true,
+                "--size" => {
+                    let value = args.next().ok_or("--size needs a value")?;
+                    options.size = Some(window_size(&value)?);
+                }
+                _ => return Err(format!("unknown option {flag}")),
+            }
+        }
+        Ok(options)
+    }
+
+    /// Parse `<digits> [options]`: the digits to show, e.g. `1234` or `12:34`, with spaces for
+    /// blanks and colons ignored, then everything [`Self::from_args`] takes
+    pub fn show_from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let value = args.next().ok_or("no digits given")?;
+        let digits: Vec<_> = value
+            .chars()
+            .filter(|&c| c != ':')
+            .map(Digit::try_from)
+            .collect::<Result<_, _>>()?;
+        if digits.is_empty() {
+            return Err(format!("no digits in {value:?}"));
+        }
+        Ok(Self {
+            show: Some(digits),
+            ..Self::from_args(args)?
+        })
+    }
+
+    /// Parse `<duration> [options]`: what to count down from, as for [`parse_duration`], then
+    /// everything [`Self::from_args`] takes
+    pub fn countdown_from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let duration = parse_duration(&args.next().ok_or("no duration given")?)?;
+        Ok(Self {
+            countdown: Some(duration),
+            ..Self::from_args(args)?
+        })
+    }
+}
+
+/// Parse `<width>x<height>`, each more than 0
+fn window_size(s: &str) -> Result<(u32, u32), String> {
+    let bad_value = || format!("expected <width>x<height>, got {s:?}");
+    let (w, h) = s.split_once('x').ok_or_else(bad_value)?;
+    match (w.trim().parse(), h.trim().parse()) {
+        (Ok(w), Ok(h)) if w > 0 && h > 0 => Ok((w, h)),
+        _ => Err(bad_value()),
+    }
+}
+
+pub struct Window {
+    pub model: Model,
+    pub config: Config,
+    /// Just [`Config::background`]'s color if there isn't one
+    pub background: Option<Background>,
+    /// Reloading the config and assets as they change, if that's possible
+    watcher: Option<Watcher>,
+}
+
+pub struct Background {
+    pub texture: wgpu::Texture,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Window {
+    fn new(app: &App) -> Self {
+        let Startup {
+            config,
+            config_path,
+            options,
+        } = STARTUP.get_or_init(|| Startup {
+            config: Config::default(),
+            config_path: config::path(),
+            options: WindowOptions::default(),
+        });
+
+        let mut window = app.new_window().title("klox").view(view);
+        if let Some((w, h)) = options.size {
+            window = window.size(w, h);
+        }
+        if options.fullscreen {
+            window = window.fullscreen();
+        }
+        window.build().expect("couldn't open a window");
+
+        let watcher = Watcher::new(config_path.clone(), assets::dir())
+            .map_err(|e| warn!("not watching for changes: {e}"))
+            .ok();
+        let mut model = Model::from_config(config);
+        if let Some(digits) = &options.show {
+            model.freeze(digits);
+        }
+        if let Some(duration) = options.countdown {
+            model.count_down(duration);
+        }
+        if options.stopwatch {
+            model.time_with_stopwatch();
+        }
+        let config = config.clone();
+        Self {
+            model,
+            background: Background::load(app, &config),
+            config,
+            watcher,
+        }
+    }
+
+    /// Apply any changes to the config and assets, keeping the clock where it is
+    fn reload(&mut self, app: &App) {
+        let Some(watcher) = &self.watcher else {
+            return;
+        };
+        let changes = watcher.changes();
+        let mut reload_background = changes.assets;
+        if let Some(config) = changes.config {
+            self.model.configure(&config);
+            reload_background |= config.background.image != self.config.background.image;
+            self.config = config;
+        }
+        if reload_background {
+            self.background = Background::load(app, &self.config);
+        }
+    }
+}
+
+impl Background {
+    /// A texture of whatever `config` says to draw behind the clock, if anything
+    fn load(app: &App, config: &Config) -> Option<Self> {
+        config.background.image.image().map(|img| {
+            let (w, h) = img.dimensions();
+            Self {
+                texture: wgpu::Texture::from_image(app, &img),
+                width: w as f32,
+                height: h as f32,
+            }
+        })
+    }
+}
+
+impl Renderer for Draw {
+    fn face(&mut self, centre: geom::Point2, radius: f32, color: render::Color) {
+        self.ellipse()
+            .xy(centre)
+            .w_h(radius * 2.0, radius * 2.0)
+            .color(to_nannou(color));
+    }
+
+    fn hand(&mut self, start: geom::Point2, end: geom::Point2, weight: f32, color: render::Color) {
+        self.line()
+            .weight(weight)
+            .start(start)
+            .end(end)
+            .color(to_nannou(color));
+    }
+}
+
+fn to_nannou(color: render::Color) -> Srgba<u8> {
+    let [r, g, b, a] = color.0;
+    srgba8(r, g, b, a)
+}
+
+pub fn app() -> nannou::app::Builder<Window> {
+    nannou::app(Window::new).event(event)
+}
+
+/// Open the window and tell the time (or show [`WindowOptions::show`]) until it's closed,
+/// reloading `config` from `config_path` whenever that changes
+pub fn run(config: Config, config_path: Option<PathBuf>, options: WindowOptions) {
+    // Only the first call counts, and there's only ever one window
+    let _ = STARTUP.set(Startup {
+        config,
+        config_path,
+        options,
+    });
+    app().run();
+}
+
+fn view(app: &App, window: &Window, frame: Frame) {
+    // // get canvas to draw on
+    let mut draw = app.draw();
+
+    let win = app.window_rect();
+    let (win_w, win_h) = (win.w(), win.h());
+
+    draw.background()
+        .color(to_nannou(window.config.background.color));
+    if let Some(background) = &window.background {
+        // --- COVER FIT (fill & crop; preserves aspect ratio) ---
+        // Scale so the smaller axis fits, then crop the overflow.
+        let sx = win_w / background.width;
+        let sy = win_h / background.height;
+        let scale = sx.max(sy); // cover
+        let target_w = background.width * scale;
+        let target_h = background.height * scale;
+
+        // set background image
+        draw.texture(&background.texture)
+            .xy(win.xy()) // center in the window
+            .w_h(target_w, target_h);
+    }
+
+    // render our Model
+    let bounds = geom::Rect::from_xy_wh(win.xy(), win.wh());
+    window.model.draw(bounds, &mut draw);
+
+    // put everything on the frame
+    draw.to_frame(app, &frame).unwrap();
+}
+
+fn event(app: &App, window: &mut Window, event: Event) {
+    if let Event::Update(_) = event {
+        window.reload(app);
+    }
+    let model = &mut window.model;
+    // Typed characters come through as they're typed, whatever the layout
+    let key = match event {
+        Event::WindowEvent {
+            simple: Some(WindowEvent::ReceivedCharacter(c)),
+            ..
+        } if !c.is_control() => Some(keys::Key::Char(c)),
+        Event::WindowEvent {
+            simple: Some(WindowEvent::KeyPressed(key)),
+            ..
+        } => match key {
+            Key::Return => Some(keys::Key::Enter),
+            Key::Back => Some(keys::Key::Backspace),
+            Key::Escape => Some(keys::Key::Escape),
+            _ => None,
+        },
+        _ => None,
+    };
+    match key.and_then(Action::for_key) {
+        Some(Action::Quit) => app.quit(),
+        Some(action) => action.apply(model),
+        None => {}
+    }
+    if let Event::Update(update) = event {
+        model.update(update.since_start);
+    }
+}